@@ -0,0 +1,186 @@
+// Copyright 2015 Valentin Vasilyev.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+
+Derives `ToSolrDocument` and `FromSolrDocument` of heliotrope for structs with named fields.
+
+Fields are mapped to Solr fields of the same name, their types implementing
+`ToSolrValue`/`FromSolrValue`. `Option` fields are omitted when `None` and
+`Vec` fields are multi-valued. Field attributes:
+
+* `#[solr(rename = "title_t")]` maps the field to a Solr field with a different name
+* `#[solr(dynamic = "attr_*")]` maps a `BTreeMap<String, T>` or `HashMap<String, T>` to dynamic fields,
+  keyed by the part of the field name matching `*`, fields mapped explicitly and `_version_` are left out
+* `#[solr(skip)]` leaves the field out, it is set to `Default::default()` when converting back
+
+```ignore
+#[macro_use]
+extern crate heliotrope_derive;
+extern crate heliotrope;
+
+#[derive(ToSolrDocument, FromSolrDocument)]
+struct Book {
+    id: String,
+    #[solr(rename = "title_t")]
+    title: String,
+    pages: Option<i64>,
+    authors: Vec<String>
+}
+```
+*/
+
+#![crate_name="heliotrope_derive"]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate syn;
+#[macro_use]
+extern crate quote;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Fields, Ident, Lit, Meta, NestedMeta};
+
+/// How a struct field is mapped
+enum Mapping {
+    /// Solr field of a given name
+    Field(String),
+    /// Dynamic fields matching a pattern
+    Dynamic(String),
+    Skip
+}
+
+struct SolrField {
+    ident: Ident,
+    mapping: Mapping
+}
+
+#[proc_macro_derive(ToSolrDocument, attributes(solr))]
+pub fn derive_to_solr_document(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let fields = match solr_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into()
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let adds = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match field.mapping {
+            Mapping::Field(ref solr_name) => quote! {
+                if let Some(value) = ::heliotrope::ToSolrValue::to_solr_value(&self.#ident) {
+                    document.fields.push(::heliotrope::SolrField{name: #solr_name.to_string(), value: value});
+                }
+            },
+            Mapping::Dynamic(ref pattern) => quote! {
+                ::heliotrope::DynamicFields::add_dynamic_fields(&self.#ident, #pattern, &mut document);
+            },
+            Mapping::Skip => TokenStream2::new()
+        }
+    });
+
+    let expanded = quote! {
+        impl #impl_generics ::heliotrope::ToSolrDocument for #name #ty_generics #where_clause {
+            fn to_solr_document(&self) -> ::heliotrope::SolrDocument {
+                let mut document = ::heliotrope::SolrDocument::new();
+                #(#adds)*
+                document
+            }
+        }
+    };
+    expanded.into()
+}
+
+#[proc_macro_derive(FromSolrDocument, attributes(solr))]
+pub fn derive_from_solr_document(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let fields = match solr_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into()
+    };
+    let name = &input.ident;
+    let struct_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // fields mapped explicitly and _version_ are not collected by dynamic field patterns
+    let mut excluded: Vec<&str> = fields.iter().filter_map(|field| match field.mapping {
+        Mapping::Field(ref solr_name) => Some(&solr_name[..]),
+        _ => None
+    }).collect();
+    excluded.push("_version_");
+
+    let inits = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let (value, solr_name) = match field.mapping {
+            Mapping::Field(ref solr_name) =>
+                (quote!(::heliotrope::FromSolrValue::from_solr_value(document.get_merged(#solr_name).as_ref().map(|v| &**v))), solr_name),
+            Mapping::Dynamic(ref pattern) =>
+                (quote!(::heliotrope::DynamicFields::from_dynamic_fields(#pattern, &[#(#excluded),*], document)), pattern),
+            Mapping::Skip => return quote!(#ident: ::std::default::Default::default())
+        };
+        quote! {
+            #ident: match #value {
+                Ok(value) => value,
+                Err(err) => return Err(::heliotrope::SolrError{status: 0, time: 0,
+                    message: format!("Field {} of {}: {}", #solr_name, #struct_name, err), kind: ::heliotrope::SolrErrorKind::Other})
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl #impl_generics ::heliotrope::FromSolrDocument for #name #ty_generics #where_clause {
+            fn from_solr_document(document: &::heliotrope::SolrDocument) -> Result<Self, ::heliotrope::SolrError> {
+                Ok(#name {
+                    #(#inits),*
+                })
+            }
+        }
+    };
+    expanded.into()
+}
+
+/// Collects named fields of the struct with their mapping
+fn solr_fields(input: &DeriveInput) -> Result<Vec<SolrField>, syn::Error> {
+    let named = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref named) => named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "Solr mapping can only be derived for structs with named fields"))
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "Solr mapping can only be derived for structs"))
+    };
+    let mut fields = Vec::with_capacity(named.named.len());
+    for field in named.named.iter() {
+        let ident = field.ident.clone().unwrap();
+        let mut mapping = Mapping::Field(ident.to_string().trim_start_matches("r#").to_string());
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("solr")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected #[solr(...)]"))
+            };
+            for nested in list.nested.iter() {
+                mapping = match *nested {
+                    NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("skip") => Mapping::Skip,
+                    NestedMeta::Meta(Meta::NameValue(ref nv)) => match nv.lit {
+                        Lit::Str(ref s) if nv.path.is_ident("rename") => Mapping::Field(s.value()),
+                        Lit::Str(ref s) if nv.path.is_ident("dynamic") => {
+                            if !s.value().contains('*') {
+                                return Err(syn::Error::new_spanned(s, "dynamic field pattern must contain *"));
+                            }
+                            Mapping::Dynamic(s.value())
+                        },
+                        _ => return Err(syn::Error::new_spanned(nv, "unknown solr attribute"))
+                    },
+                    _ => return Err(syn::Error::new_spanned(nested, "unknown solr attribute"))
+                };
+            }
+        }
+        fields.push(SolrField{ident, mapping});
+    }
+    Ok(fields)
+}
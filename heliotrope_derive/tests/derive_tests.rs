@@ -0,0 +1,140 @@
+#[macro_use]
+extern crate heliotrope_derive;
+extern crate heliotrope;
+extern crate time;
+
+use std::collections::BTreeMap;
+use heliotrope::{SolrDocument, SolrValue, ToSolrDocument, FromSolrDocument};
+use time::Timespec;
+
+#[derive(Debug, PartialEq, ToSolrDocument, FromSolrDocument)]
+struct Book {
+    id: String,
+    #[solr(rename = "title_t")]
+    title: String,
+    pages: Option<i64>,
+    price: f64,
+    authors: Vec<String>,
+    #[solr(dynamic = "attr_*")]
+    attributes: BTreeMap<String, String>,
+    #[solr(skip)]
+    cached: bool
+}
+
+fn book() -> Book {
+    let mut attributes = BTreeMap::new();
+    attributes.insert("color".to_string(), "red".to_string());
+    Book{id: "1".to_string(), title: "How to train your dragon".to_string(), pages: None, price: 9.5,
+         authors: vec!("Cressida Cowell".to_string()), attributes: attributes, cached: true}
+}
+
+#[test]
+fn to_solr_document_should_map_fields(){
+    let doc = book().to_solr_document();
+    assert_eq!(Some(&SolrValue::String("1".to_string())), doc.get("id"));
+    assert_eq!(Some(&SolrValue::String("How to train your dragon".to_string())), doc.get("title_t"));
+    assert_eq!(Some(&SolrValue::F64(9.5)), doc.get("price"));
+    assert_eq!(Some(&SolrValue::Array(vec!(SolrValue::String("Cressida Cowell".to_string())))), doc.get("authors"));
+    assert_eq!(Some(&SolrValue::String("red".to_string())), doc.get("attr_color"));
+    assert_eq!(None, doc.get("pages"));
+    assert_eq!(None, doc.get("cached"));
+    assert_eq!(None, doc.get("title"));
+}
+
+#[test]
+fn from_solr_document_should_round_trip(){
+    let mut expected = book();
+    let doc = expected.to_solr_document();
+    expected.cached = false;
+    assert_eq!(expected, Book::from_solr_document(&doc).ok().unwrap());
+}
+
+#[test]
+fn from_solr_document_should_accept_single_value_for_vec(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "2");
+    doc.add_field("title_t", "Moby Dick");
+    doc.add_i64("pages", 635);
+    doc.add_i64("price", 12);
+    doc.add_field("authors", "Herman Melville");
+    let book = Book::from_solr_document(&doc).ok().unwrap();
+    assert_eq!(Some(635), book.pages);
+    assert_eq!(12.0, book.price);
+    assert_eq!(vec!("Herman Melville".to_string()), book.authors);
+    assert!(book.attributes.is_empty());
+}
+
+#[test]
+fn from_solr_document_should_fail_on_missing_field(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "3");
+    doc.add_f64("price", 1.0);
+    let err = Book::from_solr_document(&doc).err().unwrap();
+    assert_eq!("Field title_t of Book: field not found", err.message);
+}
+
+#[test]
+fn from_solr_document_should_fail_on_type_mismatch(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "4");
+    doc.add_field("title_t", "Moby Dick");
+    doc.add_field("pages", "many");
+    doc.add_f64("price", 1.0);
+    let err = Book::from_solr_document(&doc).err().unwrap();
+    assert_eq!("Field pages of Book: expected i64, found String(\"many\")", err.message);
+}
+
+#[derive(Debug, PartialEq, ToSolrDocument, FromSolrDocument)]
+struct Event {
+    id: u32,
+    published: time::Tm,
+    #[solr(dynamic = "*_i")]
+    counts: BTreeMap<String, i32>
+}
+
+#[test]
+fn dates_and_numeric_dynamic_fields_should_round_trip(){
+    let mut counts = BTreeMap::new();
+    counts.insert("views".to_string(), 10);
+    counts.insert("likes".to_string(), -1);
+    let event = Event{id: 7, published: time::at_utc(Timespec::new(1430483400, 0)), counts: counts};
+    let doc = event.to_solr_document();
+    assert_eq!(Some(&SolrValue::String("2015-05-01T12:30:00Z".to_string())), doc.get("published"));
+    assert_eq!(Some(&SolrValue::I64(10)), doc.get("views_i"));
+    let parsed = Event::from_solr_document(&doc).ok().unwrap();
+    assert_eq!(event.counts, parsed.counts);
+    assert_eq!(event.published.to_timespec(), parsed.published.to_timespec());
+}
+
+#[test]
+fn from_solr_document_should_merge_repeated_fields(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "5");
+    doc.add_field("title_t", "Good Omens");
+    doc.add_f64("price", 8.0);
+    doc.add_field("authors", "Terry Pratchett");
+    doc.add_field("authors", "Neil Gaiman");
+    let book = Book::from_solr_document(&doc).ok().unwrap();
+    assert_eq!(vec!("Terry Pratchett".to_string(), "Neil Gaiman".to_string()), book.authors);
+}
+
+#[derive(Debug, PartialEq, ToSolrDocument, FromSolrDocument)]
+struct Article {
+    id: String,
+    #[solr(rename = "title_t")]
+    title: String,
+    #[solr(dynamic = "*_t")]
+    texts: BTreeMap<String, String>
+}
+
+#[test]
+fn dynamic_fields_should_skip_explicitly_mapped_fields(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "3");
+    doc.add_field("title_t", "Dragons");
+    doc.add_field("summary_t", "Vikings");
+    doc.add_u64("_version_", 1628);
+    let article = Article::from_solr_document(&doc).ok().unwrap();
+    assert_eq!("Dragons", article.title);
+    assert_eq!(vec!("summary"), article.texts.keys().map(|x| &x[..]).collect::<Vec<&str>>());
+}
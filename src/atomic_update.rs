@@ -0,0 +1,232 @@
+use rustc_serialize::{Encodable, Encoder};
+use document::{SolrValue, DocumentVersion};
+use mapping::ToSolrValue;
+
+/// Modifier of an atomic update
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpdateModifier {
+    /// Replaces the value, removes the field when null
+    Set,
+    /// Adds values to a multi-valued field
+    Add,
+    /// Adds values to a multi-valued field, when not already present
+    AddDistinct,
+    /// Removes all occurrences of values from a multi-valued field
+    Remove,
+    /// Removes values matching a java regular expression from a multi-valued field
+    RemoveRegex,
+    /// Increments a numeric value, by a negative amount to decrement
+    Inc
+}
+
+impl UpdateModifier {
+    /// Name of the modifier in the JSON update format
+    pub fn name(&self) -> &'static str {
+        match *self {
+            UpdateModifier::Set => "set",
+            UpdateModifier::Add => "add",
+            UpdateModifier::AddDistinct => "add-distinct",
+            UpdateModifier::Remove => "remove",
+            UpdateModifier::RemoveRegex => "removeregex",
+            UpdateModifier::Inc => "inc"
+        }
+    }
+}
+
+/// Single field modification of an atomic update
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldUpdate {
+    pub field: String,
+    pub modifier: UpdateModifier,
+    /// Value of the modifier, `SolrValue::Null` being sent as JSON null
+    pub value: SolrValue
+}
+
+/// Represents atomic (partial) update of an existing document, identified by its unique key.
+/// Fields not mentioned are kept as they are, which requires them to be stored or docValues.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtomicUpdate {
+    /// Name of the unique key field, `id` by default
+    pub unique_key: String,
+    pub id: String,
+    /// Expected `_version_` of the document
+    pub version: Option<DocumentVersion>,
+    pub updates: Vec<FieldUpdate>
+}
+
+impl AtomicUpdate {
+    /// Creates atomic update of a document with a given id
+    pub fn new(id: &str) -> AtomicUpdate {
+        AtomicUpdate{unique_key: "id".to_string(), id: id.to_string(), version: None, updates: Vec::new()}
+    }
+
+    /// Sets name of the unique key field, when other than `id`
+    pub fn unique_key(&self, unique_key: &str) -> AtomicUpdate {
+        let mut update = self.clone();
+        update.unique_key = unique_key.to_string();
+        update
+    }
+
+    /// Sets expected `_version_`, on mismatch Solr rejects the update with a version conflict error
+    pub fn version(&self, version: DocumentVersion) -> AtomicUpdate {
+        let mut update = self.clone();
+        update.version = Some(version);
+        update
+    }
+
+    /// Replaces field value, a `None` value removes the field
+    pub fn set<V: ToSolrValue>(&self, field: &str, value: V) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::Set, value)
+    }
+
+    /// Removes the field from the document
+    pub fn unset(&self, field: &str) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::Set, SolrValue::Null)
+    }
+
+    /// Adds value, or a vector of values, to a multi-valued field
+    pub fn add<V: ToSolrValue>(&self, field: &str, value: V) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::Add, value)
+    }
+
+    /// Adds value, or a vector of values, to a multi-valued field when not already present
+    pub fn add_distinct<V: ToSolrValue>(&self, field: &str, value: V) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::AddDistinct, value)
+    }
+
+    /// Removes all occurrences of value, or a vector of values, from a multi-valued field
+    pub fn remove<V: ToSolrValue>(&self, field: &str, value: V) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::Remove, value)
+    }
+
+    /// Removes values matching java regular expression from a multi-valued field
+    pub fn remove_regex(&self, field: &str, regex: &str) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::RemoveRegex, regex)
+    }
+
+    /// Increments numeric field by amount, repeated increments of the same field are summed
+    pub fn inc<V: ToSolrValue>(&self, field: &str, amount: V) -> AtomicUpdate {
+        self.modify(field, UpdateModifier::Inc, amount)
+    }
+
+    fn modify<V: ToSolrValue>(&self, field: &str, modifier: UpdateModifier, value: V) -> AtomicUpdate {
+        let mut update = self.clone();
+        let value = value.to_solr_value().unwrap_or(SolrValue::Null);
+        // single valued modifiers can't be merged into an array, the last set wins and increments are summed
+        if modifier == UpdateModifier::Set || modifier == UpdateModifier::Inc {
+            if let Some(previous) = update.updates.iter_mut().find(|u| u.field == field && u.modifier == modifier) {
+                previous.value = match modifier {
+                    UpdateModifier::Inc => sum_amounts(&previous.value, &value).unwrap_or(value),
+                    _ => value
+                };
+                return update;
+            }
+        }
+        update.updates.push(FieldUpdate{field: field.to_string(), modifier: modifier, value: value});
+        update
+    }
+
+    /// Names of the updated fields in order of their first occurrence
+    fn field_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::with_capacity(self.updates.len());
+        for update in self.updates.iter() {
+            if !names.contains(&&update.field[..]) {
+                names.push(&update.field);
+            }
+        }
+        names
+    }
+}
+
+/// Sums two increments, falling back to floating point when integers overflow, `None` for non-numeric values
+fn sum_amounts(a: &SolrValue, b: &SolrValue) -> Option<SolrValue> {
+    let sum = match (a, b) {
+        (&SolrValue::I64(x), &SolrValue::I64(y)) => x.checked_add(y).map(SolrValue::I64),
+        (&SolrValue::U64(x), &SolrValue::U64(y)) => x.checked_add(y).map(SolrValue::U64),
+        (&SolrValue::I64(x), &SolrValue::U64(y)) | (&SolrValue::U64(y), &SolrValue::I64(x)) if y <= i64::max_value() as u64 =>
+            x.checked_add(y as i64).map(SolrValue::I64),
+        _ => None
+    };
+    match (to_f64(a), to_f64(b)) {
+        (Some(x), Some(y)) => sum.or(Some(SolrValue::F64(x + y))),
+        _ => None
+    }
+}
+
+fn to_f64(value: &SolrValue) -> Option<f64> {
+    match *value {
+        SolrValue::I64(v) => Some(v as f64),
+        SolrValue::U64(v) => Some(v as f64),
+        SolrValue::F64(v) => Some(v),
+        _ => None
+    }
+}
+
+// Encodes as {"id":"1","views":{"inc":1},"tags":{"add":["a"],"remove":"b"}},
+// modifications of the same field are merged into one object
+impl Encodable for AtomicUpdate {
+    fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+        let names = self.field_names();
+        let len = if self.version.is_some() { names.len() + 2 } else { names.len() + 1 };
+        e.emit_struct("AtomicUpdate", len, |e| {
+            let mut i = 0usize;
+            try!(e.emit_struct_field(&self.unique_key, i, |e| self.id.encode(e)));
+            if let Some(version) = self.version {
+                i = i + 1;
+                try!(e.emit_struct_field("_version_", i, |e| version.value().encode(e)));
+            }
+            for name in names.iter() {
+                i = i + 1;
+                let updates: Vec<&FieldUpdate> = self.updates.iter().filter(|u| u.field == *name).collect();
+                try!(e.emit_struct_field(name, i, |e| encode_modifiers(&updates, e)));
+            }
+            Ok(())
+        })
+    }
+}
+
+fn encode_modifiers<E: Encoder>(updates: &[&FieldUpdate], e: &mut E) -> Result<(), E::Error> {
+    let mut modifiers: Vec<UpdateModifier> = Vec::with_capacity(updates.len());
+    for update in updates.iter() {
+        if !modifiers.contains(&update.modifier) {
+            modifiers.push(update.modifier);
+        }
+    }
+    e.emit_struct("FieldUpdate", modifiers.len(), |e| {
+        for (i, modifier) in modifiers.iter().enumerate() {
+            let values: Vec<&SolrValue> = updates.iter().filter(|u| u.modifier == *modifier).map(|u| &u.value).collect();
+            try!(e.emit_struct_field(modifier.name(), i, |e| {
+                if values.len() == 1 {
+                    encode_value(values[0], e)
+                } else {
+                    encode_values(&values, e)
+                }
+            }));
+        }
+        Ok(())
+    })
+}
+
+/// Encodes several values of the same multi-valued modifier as one array, flattening vectors of values
+fn encode_values<E: Encoder>(values: &[&SolrValue], e: &mut E) -> Result<(), E::Error> {
+    let mut flattened: Vec<&SolrValue> = Vec::with_capacity(values.len());
+    for value in values.iter() {
+        match **value {
+            SolrValue::Array(ref v) => flattened.extend(v.iter()),
+            ref v => flattened.push(v)
+        }
+    }
+    e.emit_seq(flattened.len(), |e| {
+        for (i, value) in flattened.iter().enumerate() {
+            try!(e.emit_seq_elt(i, |e| encode_value(value, e)));
+        }
+        Ok(())
+    })
+}
+
+fn encode_value<E: Encoder>(value: &SolrValue, e: &mut E) -> Result<(), E::Error> {
+    match *value {
+        SolrValue::Null => e.emit_nil(),
+        ref v => v.encode(e)
+    }
+}
@@ -0,0 +1,67 @@
+use query::ToUrlParam;
+use query_dsl::format_param_value;
+
+/// Represents [child] document transformer, returning nested child documents of each result
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChildTransformer {
+    /// Query selecting all parent documents, not needed with nested schema (Solr 8+)
+    pub parent_filter: Option<String>,
+    pub child_filter: Option<String>,
+    pub limit: Option<i32>,
+    pub fields: Vec<String>
+}
+
+impl ChildTransformer {
+    /// Creates [child] transformer with Solr defaults
+    pub fn new() -> ChildTransformer {
+        ChildTransformer::default()
+    }
+
+    /// Sets query selecting all parent documents (parentFilter), for example `type:product`
+    pub fn parent_filter(&self, parent_filter: &str) -> ChildTransformer {
+        let mut transformer = self.clone();
+        transformer.parent_filter = Some(parent_filter.to_string());
+        transformer
+    }
+
+    /// Sets query selecting returned child documents (childFilter)
+    pub fn child_filter(&self, child_filter: &str) -> ChildTransformer {
+        let mut transformer = self.clone();
+        transformer.child_filter = Some(child_filter.to_string());
+        transformer
+    }
+
+    /// Sets maximum number of child documents per parent, -1 meaning unlimited (limit)
+    pub fn limit(&self, limit: i32) -> ChildTransformer {
+        let mut transformer = self.clone();
+        transformer.limit = Some(limit);
+        transformer
+    }
+
+    /// Adds field returned for child documents (fl)
+    pub fn add_field(&self, field: &str) -> ChildTransformer {
+        let mut transformer = self.clone();
+        transformer.fields.push(field.to_string());
+        transformer
+    }
+}
+
+impl ToUrlParam for ChildTransformer {
+    fn to_url_param(&self) -> String {
+        let mut param = "[child".to_string();
+        if let Some(ref parent_filter) = self.parent_filter {
+            param.push_str(&format!(" parentFilter={}", format_param_value(parent_filter)));
+        }
+        if let Some(ref child_filter) = self.child_filter {
+            param.push_str(&format!(" childFilter={}", format_param_value(child_filter)));
+        }
+        if let Some(limit) = self.limit {
+            param.push_str(&format!(" limit={}", limit));
+        }
+        if !self.fields.is_empty() {
+            param.push_str(&format!(" fl={}", format_param_value(&self.fields.join(","))));
+        }
+        param.push(']');
+        param
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use url::Url;
+use rustc_serialize::json;
+use http_utils;
+use document::{SolrDocument, SolrValue};
+use response::{SolrError, SolrErrorKind, SolrUpdateResponse};
+
+static MAX_RETRY_DELAY_SECS: u64 = 60;
+
+/// Configuration of bulk indexing, documents are sent in batches from a pool of worker threads.
+/// Batches waiting for a worker are limited by `queue_size`, so adding documents blocks
+/// while the workers are busy instead of buffering the whole stream in memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkIndexer {
+    pub update_url: Url,
+    /// Maximum number of documents in a batch, 500 by default
+    pub batch_size: usize,
+    /// Maximum size of a batch JSON body in bytes, 5 MB by default.
+    /// A single bigger document is sent alone.
+    pub batch_bytes: usize,
+    /// Number of worker threads, 4 by default
+    pub workers: usize,
+    /// Maximum number of batches waiting for a worker, 8 by default
+    pub queue_size: usize,
+    /// Number of retries of a batch failing with network or server (5xx) error, 3 by default
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled with every next one up to a minute, 500 ms by default
+    pub retry_delay: Duration,
+    pub commit_within: Option<u32>,
+    /// Field identifying failed documents, `id` by default
+    pub unique_key: String
+}
+
+impl BulkIndexer {
+    /// Creates bulk indexer sending documents to the update handler of a core
+    pub fn new(base_url: &Url) -> BulkIndexer {
+        BulkIndexer{update_url: base_url.join("./update").unwrap(),
+                    batch_size: 500,
+                    batch_bytes: 5 * 1024 * 1024,
+                    workers: 4,
+                    queue_size: 8,
+                    max_retries: 3,
+                    retry_delay: Duration::from_millis(500),
+                    commit_within: None,
+                    unique_key: "id".to_string()}
+    }
+
+    pub fn batch_size(&self, batch_size: usize) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        indexer
+    }
+
+    pub fn batch_bytes(&self, batch_bytes: usize) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.batch_bytes = batch_bytes;
+        indexer
+    }
+
+    pub fn workers(&self, workers: usize) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.workers = if workers == 0 { 1 } else { workers };
+        indexer
+    }
+
+    pub fn queue_size(&self, queue_size: usize) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.queue_size = queue_size;
+        indexer
+    }
+
+    pub fn max_retries(&self, max_retries: u32) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.max_retries = max_retries;
+        indexer
+    }
+
+    pub fn retry_delay(&self, retry_delay: Duration) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.retry_delay = retry_delay;
+        indexer
+    }
+
+    /// Commits documents within the given number of milliseconds
+    pub fn commit_within(&self, milliseconds: u32) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.commit_within = Some(milliseconds);
+        indexer
+    }
+
+    pub fn unique_key(&self, unique_key: &str) -> BulkIndexer {
+        let mut indexer = self.clone();
+        indexer.unique_key = unique_key.to_string();
+        indexer
+    }
+
+    /// Starts worker threads, documents are then added to the returned session
+    pub fn start(&self) -> BulkSession {
+        let (sender, receiver) = mpsc::sync_channel::<(u64, Batch)>(self.queue_size);
+        let (result_sender, results) = mpsc::channel::<BatchResult>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let pending: Pending = Arc::new(Mutex::new(BTreeMap::new()));
+        let workers = (0..self.workers).map(|_| {
+            let indexer = self.clone();
+            let receiver = receiver.clone();
+            let result_sender = result_sender.clone();
+            let pending = pending.clone();
+            thread::spawn(move || work(&indexer, &receiver, &result_sender, &pending))
+        }).collect();
+        BulkSession{indexer: self.clone(), sender: Some(sender), results: results, workers: workers, pending: pending,
+                    batch: Batch::new(), started: Instant::now(), stats: BulkIndexStats::default()}
+    }
+
+    /// Indexes all documents of the stream, blocking until done
+    pub fn index<I: IntoIterator<Item=SolrDocument>>(&self, documents: I) -> BulkIndexStats {
+        let mut session = self.start();
+        for document in documents {
+            if session.add(&document).is_err() {
+                break;
+            }
+        }
+        session.finish()
+    }
+}
+
+/// Document which failed to be indexed
+#[derive(Clone, Debug)]
+pub struct DocumentFailure {
+    /// Unique key of the document, when present
+    pub id: Option<String>,
+    pub error: SolrError
+}
+
+/// Statistics of bulk indexing
+#[derive(Clone, Debug, Default)]
+pub struct BulkIndexStats {
+    /// Number of added documents
+    pub documents: u64,
+    pub indexed: u64,
+    pub failed: u64,
+    /// Number of sent batches, without retries
+    pub batches: u64,
+    pub retries: u64,
+    /// Size of sent batches in bytes, without retries
+    pub bytes: u64,
+    pub elapsed: Duration,
+    pub failures: Vec<DocumentFailure>
+}
+
+impl BulkIndexStats {
+    /// Indexed documents per second
+    pub fn docs_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs() as f64 + self.elapsed.subsec_nanos() as f64 / 1e9;
+        if seconds > 0.0 { self.indexed as f64 / seconds } else { 0.0 }
+    }
+
+    fn merge(&mut self, result: BatchResult) {
+        self.indexed += result.indexed;
+        self.failed += result.failures.len() as u64;
+        self.retries += result.retries;
+        self.failures.extend(result.failures);
+    }
+}
+
+/// Running bulk indexing, created by `BulkIndexer::start`
+pub struct BulkSession {
+    indexer: BulkIndexer,
+    sender: Option<SyncSender<(u64, Batch)>>,
+    results: Receiver<BatchResult>,
+    workers: Vec<JoinHandle<()>>,
+    pending: Pending,
+    batch: Batch,
+    started: Instant,
+    stats: BulkIndexStats
+}
+
+impl BulkSession {
+    /// Adds a document to the current batch, blocking while the queue of batches is full.
+    /// Fails only when the workers are gone.
+    pub fn add(&mut self, document: &SolrDocument) -> Result<(), SolrError> {
+        self.stats.documents += 1;
+        let id = unique_key(document, &self.indexer.unique_key);
+        let doc_json = match json::encode(document) {
+            Ok(doc_json) => doc_json,
+            Err(err) => {
+                self.stats.failed += 1;
+                self.stats.failures.push(DocumentFailure{id: id, error: SolrError{status: 0, time: 0,
+                    message: format!("Error serializing solr document to json: {}", err), kind: SolrErrorKind::Other}});
+                return Ok(());
+            }
+        };
+        if !self.batch.docs.is_empty() && self.batch.bytes + doc_json.len() + 1 > self.indexer.batch_bytes {
+            try!(self.flush());
+        }
+        self.batch.push(id, doc_json);
+        if self.batch.docs.len() >= self.indexer.batch_size {
+            try!(self.flush());
+        }
+        Ok(())
+    }
+
+    /// Statistics of batches completed so far
+    pub fn progress(&mut self) -> &BulkIndexStats {
+        while let Ok(result) = self.results.try_recv() {
+            self.stats.merge(result);
+        }
+        self.stats.elapsed = self.started.elapsed();
+        &self.stats
+    }
+
+    /// Sends the last batch and waits for all workers to finish
+    pub fn finish(mut self) -> BulkIndexStats {
+        // documents of a batch which couldn't be sent are recorded as failed by flush
+        let _ = self.flush();
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+        // batches left were being sent by panicked workers or still queued when all of them panicked
+        let unfinished = ::std::mem::replace(&mut *lock(&self.pending), BTreeMap::new());
+        let error = workers_error("Bulk indexing worker panicked");
+        for (_, ids) in unfinished.into_iter() {
+            self.stats.failed += ids.len() as u64;
+            self.stats.failures.extend(ids.into_iter().map(|id| DocumentFailure{id: id, error: error.clone()}));
+        }
+        while let Ok(result) = self.results.try_recv() {
+            self.stats.merge(result);
+        }
+        self.stats.elapsed = self.started.elapsed();
+        self.stats
+    }
+
+    fn flush(&mut self) -> Result<(), SolrError> {
+        if self.batch.docs.is_empty() {
+            return Ok(());
+        }
+        let batch = ::std::mem::replace(&mut self.batch, Batch::new());
+        let bytes = batch.bytes as u64 + 1;
+        while let Ok(result) = self.results.try_recv() {
+            self.stats.merge(result);
+        }
+        // batches sent so far number the batch, a batch which couldn't be sent doesn't take a number
+        let number = self.stats.batches;
+        lock(&self.pending).insert(number, batch.docs.iter().map(|d| d.0.clone()).collect());
+        let unsent = match self.sender {
+            Some(ref sender) => sender.send((number, batch)).err().map(|e| (e.0).1),
+            None => Some(batch)
+        };
+        match unsent {
+            None => {
+                self.stats.batches += 1;
+                self.stats.bytes += bytes;
+                Ok(())
+            },
+            Some(batch) => {
+                lock(&self.pending).remove(&number);
+                let error = workers_error("Bulk indexing workers stopped");
+                self.stats.failed += batch.docs.len() as u64;
+                self.stats.failures.extend(batch.docs.into_iter().map(|(id, _)| DocumentFailure{id: id, error: error.clone()}));
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Serialized documents of a batch with their unique keys
+struct Batch {
+    docs: Vec<(Option<String>, String)>,
+    /// Size of the JSON array body, without the closing bracket
+    bytes: usize
+}
+
+impl Batch {
+    fn new() -> Batch {
+        Batch{docs: Vec::new(), bytes: 1}
+    }
+
+    fn push(&mut self, id: Option<String>, doc_json: String) {
+        self.bytes += doc_json.len() + if self.docs.is_empty() { 0 } else { 1 };
+        self.docs.push((id, doc_json));
+    }
+}
+
+/// Unique keys of the documents of batches queued or being sent, by batch number
+type Pending = Arc<Mutex<BTreeMap<u64, Vec<Option<String>>>>>;
+
+struct BatchResult {
+    indexed: u64,
+    retries: u64,
+    failures: Vec<DocumentFailure>
+}
+
+fn work(indexer: &BulkIndexer, receiver: &Mutex<Receiver<(u64, Batch)>>, results: &Sender<BatchResult>, pending: &Pending) {
+    loop {
+        let (number, batch) = match receiver.lock() {
+            Ok(receiver) => match receiver.recv() {
+                Ok(batch) => batch,
+                Err(_) => return
+            },
+            Err(_) => return
+        };
+        let result = send_batch(indexer, batch);
+        lock(pending).remove(&number);
+        if results.send(result).is_err() {
+            return;
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Sends batch, a batch rejected by Solr is resent document by document to find the failing ones.
+/// Solr keeps documents applied before the failing one, so on a version conflict only the
+/// conflicting document fails and the documents after it are sent again. Other rejections
+/// resend the whole batch, which reports already indexed documents with an expected
+/// `_version_` as conflicting.
+fn send_batch(indexer: &BulkIndexer, batch: Batch) -> BatchResult {
+    let mut result = BatchResult{indexed: 0, retries: 0, failures: Vec::new()};
+    let mut docs = batch.docs;
+    while !docs.is_empty() {
+        let body = json_array(docs.iter().map(|d| &d.1[..]));
+        let err = match send_with_retries(indexer, &body, &mut result.retries) {
+            Ok(_) => {
+                result.indexed += docs.len() as u64;
+                break;
+            },
+            Err(err) => err
+        };
+        let conflict = match err.kind {
+            SolrErrorKind::VersionConflict(ref conflict) => docs.iter().position(|d| d.0.as_ref() == Some(&conflict.id)),
+            SolrErrorKind::Other => None
+        };
+        if let Some(position) = conflict {
+            let rest = docs.split_off(position + 1);
+            let (id, _) = docs.pop().unwrap();
+            result.indexed += docs.len() as u64;
+            result.failures.push(DocumentFailure{id: id, error: err});
+            docs = rest;
+        } else if docs.len() > 1 && !is_retryable(&err) {
+            for (id, doc_json) in docs.into_iter() {
+                match send_with_retries(indexer, &json_array(Some(&doc_json[..]).into_iter()), &mut result.retries) {
+                    Ok(_) => result.indexed += 1,
+                    Err(err) => result.failures.push(DocumentFailure{id: id, error: err})
+                }
+            }
+            break;
+        } else {
+            result.failures.extend(docs.into_iter().map(|(id, _)| DocumentFailure{id: id, error: err.clone()}));
+            break;
+        }
+    }
+    result
+}
+
+fn send_with_retries(indexer: &BulkIndexer, body: &str, retries: &mut u64) -> Result<SolrUpdateResponse, SolrError> {
+    let mut url = indexer.update_url.clone();
+    if let Some(commit_within) = indexer.commit_within {
+        url.query_pairs_mut().append_pair("commitWithin", &commit_within.to_string());
+    }
+    let mut attempt = 0;
+    loop {
+        let result = match http_utils::post_json(&url, body) {
+            Ok(response) => SolrUpdateResponse::from_json_str(&response.body),
+            Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err), kind: SolrErrorKind::Other})
+        };
+        match result {
+            Err(ref err) if attempt < indexer.max_retries && is_retryable(err) => {
+                thread::sleep(backoff(indexer.retry_delay, attempt));
+                attempt += 1;
+                *retries += 1;
+            },
+            result => return result
+        }
+    }
+}
+
+/// Delay doubled with every attempt, up to a minute or the initial delay when longer
+fn backoff(retry_delay: Duration, attempt: u32) -> Duration {
+    let max_delay = ::std::cmp::max(retry_delay, Duration::from_secs(MAX_RETRY_DELAY_SECS));
+    match retry_delay.checked_mul(1u32 << ::std::cmp::min(attempt, 16)) {
+        Some(delay) => ::std::cmp::min(delay, max_delay),
+        None => max_delay
+    }
+}
+
+/// Network and server errors may succeed when retried, unlike rejected documents
+fn is_retryable(error: &SolrError) -> bool {
+    error.status == 0 || error.status >= 500
+}
+
+fn json_array<'a, I: Iterator<Item=&'a str>>(docs: I) -> String {
+    let mut body = "[".to_string();
+    for (i, doc) in docs.enumerate() {
+        if i > 0 {
+            body.push(',');
+        }
+        body.push_str(doc);
+    }
+    body.push(']');
+    body
+}
+
+fn workers_error(message: &str) -> SolrError {
+    SolrError{status: 0, time: 0, message: message.to_string(), kind: SolrErrorKind::Other}
+}
+
+fn unique_key(document: &SolrDocument, unique_key: &str) -> Option<String> {
+    match document.get(unique_key) {
+        Some(&SolrValue::String(ref s)) => Some(s.clone()),
+        Some(&SolrValue::I64(i)) => Some(i.to_string()),
+        Some(&SolrValue::U64(u)) => Some(u.to_string()),
+        _ => None
+    }
+}
@@ -5,12 +5,24 @@ use hyper::error::Error;
 use http_utils;
 use http_utils::HttpResponse;
 use document::SolrDocument;
+use atomic_update::AtomicUpdate;
+use update_request::UpdateRequest;
+use bulk::BulkIndexer;
+use update_stream::UpdateFormat;
+use std::io::Read;
+use mapping::{ToSolrDocument, FromSolrDocument};
 use query::SolrQuery;
+use suggest::SuggestQuery;
+use terms::TermsQuery;
+use more_like_this::{MoreLikeThisQuery, MoreLikeThisSeed};
 use request::SolrDeleteRequest;
-use response::SolrError;
+use cursor::SolrCursor;
+use response::{SolrError, SolrErrorKind};
 use response::{SolrPingResponse, SolrPingResult};
 use response::{SolrQueryResponse, SolrQueryResult};
 use response::{SolrUpdateResponse, SolrUpdateResult};
+use response::{SolrSuggestResponse, SolrSuggestResult};
+use response::{SolrTermsResponse, SolrTermsResult};
 
 /// Represents your API connection to Solr.
 /// You use this struct to perform operations on Solr.
@@ -77,9 +89,9 @@ impl SolrClient {
             Ok(http_response) => match SolrPingResponse::from_json_str(&http_response.body) {
                 Ok(spr) => Ok(spr),
                 // TODO: insert actual builder_error inside solr_error
-                Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Error parsing ping response JSON: {}", err.message)})
+                Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Error parsing ping response JSON: {}", err.message), kind: SolrErrorKind::Other})
             },
-            Err(_) => Err(SolrError{status: 0, time: 0, message: "Network error".to_string()})
+            Err(_) => Err(SolrError{status: 0, time: 0, message: "Network error".to_string(), kind: SolrErrorKind::Other})
         }
     }
 
@@ -97,6 +109,72 @@ impl SolrClient {
         handle_http_query_result(http_result)
     }
 
+    /// Performs Solr query, converting returned documents into typed items
+    pub fn query_as<T: FromSolrDocument>(&self, query: &SolrQuery) -> Result<Vec<T>, SolrError> {
+        let response = try!(self.query(query));
+        response.items_as()
+    }
+
+    /// Iterates over all documents matching the query using cursorMark deep paging.
+    /// Pages of `rows` documents are fetched lazily until the cursor stops changing.
+    /// The query must be sorted by `unique_key` field and must not set start.
+    /// Iteration resumes from the cursor mark of the query when set.
+    pub fn query_cursor(&self, query: &SolrQuery, unique_key: &str) -> Result<SolrCursor, SolrError> {
+        SolrCursor::new(self, query, unique_key)
+    }
+
+    /// Requests suggestions from the suggester request handler
+    pub fn suggest(&self, query: &SuggestQuery) -> SolrSuggestResult {
+        let mut suggest_url = match self.base_url.join(&format!("./{}", query.handler)) {
+            Ok(url) => url,
+            Err(err) => return Err(SolrError{status: 0, time: 0, message: format!("Invalid suggest handler {}: {}", query.handler, err), kind: SolrErrorKind::Other})
+        };
+
+        for (parameter, value) in query.to_pairs() {
+            suggest_url.query_pairs_mut().append_pair(&parameter, &value);
+        }
+
+        match http_utils::get(&suggest_url) {
+            Ok(response) => SolrSuggestResponse::from_json_str(&response.body),
+            Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err), kind: SolrErrorKind::Other})
+        }
+    }
+
+    /// Lists indexed terms of fields using the terms request handler
+    pub fn terms(&self, query: &TermsQuery) -> SolrTermsResult {
+        let mut terms_url = match self.base_url.join(&format!("./{}", query.handler)) {
+            Ok(url) => url,
+            Err(err) => return Err(SolrError{status: 0, time: 0, message: format!("Invalid terms handler {}: {}", query.handler, err), kind: SolrErrorKind::Other})
+        };
+
+        for (parameter, value) in query.to_pairs() {
+            terms_url.query_pairs_mut().append_pair(&parameter, &value);
+        }
+
+        match http_utils::get(&terms_url) {
+            Ok(response) => SolrTermsResponse::from_json_str(&response.body),
+            Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err), kind: SolrErrorKind::Other})
+        }
+    }
+
+    /// Searches documents similar to a document or raw text using the MoreLikeThis request handler
+    pub fn more_like_this(&self, query: &MoreLikeThisQuery) -> SolrQueryResult {
+        let mut mlt_url = match self.base_url.join(&format!("./{}", query.handler)) {
+            Ok(url) => url,
+            Err(err) => return Err(SolrError{status: 0, time: 0, message: format!("Invalid MoreLikeThis handler {}: {}", query.handler, err), kind: SolrErrorKind::Other})
+        };
+
+        for (parameter, value) in query.to_pairs() {
+            mlt_url.query_pairs_mut().append_pair(&parameter, &value);
+        }
+
+        let http_result = match query.seed {
+            MoreLikeThisSeed::Query(_) => http_utils::get(&mlt_url),
+            MoreLikeThisSeed::Text(ref text) => http_utils::post_text(&mlt_url, text)
+        };
+        handle_http_query_result(http_result)
+    }
+
     // TODO DRY
     /// Adds new document to Solr, without committing
     pub fn add(&self, document: &SolrDocument) -> SolrUpdateResult {
@@ -116,10 +194,17 @@ impl SolrClient {
                 let http_result =  http_utils::post_json(&self.update_url, &body);
                 handle_http_update_result(http_result)
             },
-            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string()})
+            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string(), kind: SolrErrorKind::Other})
         }
     }
 
+    /// Adds multiple typed items to Solr, without committing
+    pub fn add_items<T: ToSolrDocument>(&self, items: &[T]) -> SolrUpdateResult {
+        let documents: Vec<SolrDocument> = items.iter().map(|x| x.to_solr_document()).collect();
+        let refs: Vec<&SolrDocument> = documents.iter().collect();
+        self.add_many(&refs)
+    }
+
     /// Ads multiple documents to Solr and commits them
     pub fn add_many_and_commit(&self, documents: &[&SolrDocument]) -> SolrUpdateResult {
         let raw_json = json::encode(&documents);
@@ -128,10 +213,72 @@ impl SolrClient {
                 let http_result =  http_utils::post_json(&self.commit_url, &body);
                 handle_http_update_result(http_result)
             },
-            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string()})
+            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string(), kind: SolrErrorKind::Other})
         }
     }
 
+    /// Atomically updates fields of an existing document, without committing
+    pub fn update_fields(&self, update: &AtomicUpdate) -> SolrUpdateResult {
+        self.update_fields_many(&[update])
+    }
+
+    /// Atomically updates fields of multiple existing documents, without committing
+    pub fn update_fields_many(&self, updates: &[&AtomicUpdate]) -> SolrUpdateResult {
+        match json::encode(&updates) {
+            Ok(body) => {
+                let http_result = http_utils::post_json(&self.update_url, &body);
+                handle_http_update_result(http_result)
+            },
+            Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Error serializing atomic update to json: {}", err), kind: SolrErrorKind::Other})
+        }
+    }
+
+    /// Sends update request with its commands and commit options
+    pub fn update(&self, request: &UpdateRequest) -> SolrUpdateResult {
+        let mut update_url = self.update_url.clone();
+        for (parameter, value) in request.to_pairs() {
+            update_url.query_pairs_mut().append_pair(&parameter, &value);
+        }
+        match json::encode(request) {
+            Ok(body) => {
+                let http_result = http_utils::post_json(&update_url, &body);
+                handle_http_update_result(http_result)
+            },
+            Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Error serializing update request to json: {}", err), kind: SolrErrorKind::Other})
+        }
+    }
+
+    /// Streams update body in a given format from a reader, without committing.
+    /// The body is sent as it is read, without buffering it in memory.
+    pub fn update_stream<R: Read>(&self, format: &UpdateFormat, body: &mut R) -> SolrUpdateResult {
+        self.send_stream(format, body, false)
+    }
+
+    /// Streams update body in a given format from a reader and commits it
+    pub fn update_stream_and_commit<R: Read>(&self, format: &UpdateFormat, body: &mut R) -> SolrUpdateResult {
+        self.send_stream(format, body, true)
+    }
+
+    fn send_stream<R: Read>(&self, format: &UpdateFormat, body: &mut R, commit: bool) -> SolrUpdateResult {
+        let mut update_url = match self.base_url.join(format.handler()) {
+            Ok(url) => url,
+            Err(err) => return Err(SolrError{status: 0, time: 0, message: format!("Invalid update handler {}: {}", format.handler(), err), kind: SolrErrorKind::Other})
+        };
+        for (parameter, value) in format.to_pairs() {
+            update_url.query_pairs_mut().append_pair(&parameter, &value);
+        }
+        if commit {
+            update_url.query_pairs_mut().append_pair("commit", "true");
+        }
+        let http_result = http_utils::post_stream(&update_url, format.content_type(), body);
+        handle_http_update_result(http_result)
+    }
+
+    /// Creates bulk indexer for this core, sending batches of documents from worker threads
+    pub fn bulk_indexer(&self) -> BulkIndexer {
+        BulkIndexer::new(&self.base_url)
+    }
+
     /// Performs an explicit commit, causing pending documents to be indexed
     pub fn commit(&self) -> SolrUpdateResult {
         let http_result = http_utils::post_json(&self.commit_url, "");
@@ -151,6 +298,7 @@ impl SolrClient {
     }
 
     /// Deletes a single document by a unique ID
+    /// and commits, see `update` for deleting without commit
     pub fn delete_by_id(&self, id: &str) -> SolrUpdateResult {
         let delete_request = SolrDeleteRequest::from_id(id);
         let raw_json = json::encode(&delete_request);
@@ -159,11 +307,12 @@ impl SolrClient {
                 let http_result =  http_utils::post_json(&self.commit_url, &body);
                 handle_http_update_result(http_result)
             },
-            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string()})
+            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string(), kind: SolrErrorKind::Other})
         }
     }
 
     /// Deletes a list of documents by IDs
+    /// and commits, see `update` for deleting without commit
     pub fn delete_by_ids(&self, ids: &Vec<String>) -> SolrUpdateResult {
         let delete_request = SolrDeleteRequest::from_ids(&ids);
         let raw_json = json::encode(&delete_request);
@@ -172,11 +321,12 @@ impl SolrClient {
                 let http_result =  http_utils::post_json(&self.commit_url, &body);
                 handle_http_update_result(http_result)
             },
-            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string()})
+            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string(), kind: SolrErrorKind::Other})
         }
     }
 
     /// Deletes documents from the index by query
+    /// and commits, see `update` for deleting without commit
     pub fn delete_by_query(&self, query: &str) -> SolrUpdateResult {
         let delete_request = SolrDeleteRequest::from_query(query);
         let raw_json = json::encode(&delete_request);
@@ -185,7 +335,7 @@ impl SolrClient {
                 let http_result =  http_utils::post_json(&self.commit_url, &body);
                 handle_http_update_result(http_result)
             },
-            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string()})
+            Err(err) => Err(SolrError{status: 0, time: 0, message: "Error serialize solr document to json".to_string(), kind: SolrErrorKind::Other})
         }
     }
 }
@@ -193,12 +343,9 @@ impl SolrClient {
 fn handle_http_update_result(http_result: Result<HttpResponse, Error>) -> SolrUpdateResult {
     match http_result {
         Ok(response) => {
-            match json::decode::<SolrUpdateResponse>(&response.body) {
-                Ok(sur) => Ok(sur),
-                Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Parse error: {}", err)})
-            }
+            SolrUpdateResponse::from_json_str(&response.body)
         },
-        Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err)})
+        Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err), kind: SolrErrorKind::Other})
     }
 }
 
@@ -211,6 +358,6 @@ fn handle_http_query_result(http_result: Result<HttpResponse, Error>) -> SolrQue
                 Err(err) => Err(err)
             }
         },
-        Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err)})
+        Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Http error: {}", err), kind: SolrErrorKind::Other})
     }
 }
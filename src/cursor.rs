@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+use client::SolrClient;
+use document::SolrDocument;
+use query::SolrQuery;
+use response::{SolrError, SolrErrorKind};
+
+/// Iterator over all documents matching a query, fetched page by page using cursorMark.
+/// Created by `SolrClient::query_cursor`.
+pub struct SolrCursor<'a> {
+    client: &'a SolrClient,
+    query: SolrQuery,
+    cursor_mark: String,
+    buffer: VecDeque<SolrDocument>,
+    done: bool
+}
+
+impl<'a> SolrCursor<'a> {
+    /// Creates a new cursor, validating that the query can be used for deep paging.
+    /// Iteration starts at the cursor mark of the query, `*` when not set.
+    pub fn new(client: &'a SolrClient, query: &SolrQuery, unique_key: &str) -> Result<SolrCursor<'a>, SolrError> {
+        if !query.is_sorted_by(unique_key) {
+            return Err(SolrError{status: 0, time: 0,
+                message: format!("Cursor query must be sorted by unique key field {}", unique_key), kind: SolrErrorKind::Other});
+        }
+        if query.get_start() != 0 {
+            return Err(SolrError{status: 0, time: 0, message: "Cursor query must not set start".to_string(), kind: SolrErrorKind::Other});
+        }
+        Ok(SolrCursor{client: client,
+            query: query.clone(),
+            cursor_mark: query.get_cursor_mark().unwrap_or("*").to_string(),
+            buffer: VecDeque::new(),
+            done: false})
+    }
+
+    /// Returns cursor mark of the next page to be fetched,
+    /// which can be used to resume iteration later
+    pub fn cursor_mark(&self) -> &str {
+        &self.cursor_mark
+    }
+
+    fn fetch_page(&mut self) -> Result<(), SolrError> {
+        let response = try!(self.client.query(&self.query.cursor_mark(&self.cursor_mark)));
+        match response.next_cursor_mark {
+            Some(next) => {
+                // Solr returns the same cursor mark when there are no more documents
+                if next == self.cursor_mark {
+                    self.done = true;
+                }
+                self.cursor_mark = next;
+            },
+            None => {
+                self.done = true;
+                return Err(SolrError{status: 0, time: 0, message: "Cursor response has no nextCursorMark".to_string(), kind: SolrErrorKind::Other});
+            }
+        }
+        self.buffer.extend(response.items);
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for SolrCursor<'a> {
+    type Item = Result<SolrDocument, SolrError>;
+
+    fn next(&mut self) -> Option<Result<SolrDocument, SolrError>> {
+        loop {
+            if let Some(doc) = self.buffer.pop_front() {
+                return Some(Ok(doc));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.fetch_page() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use query::ToUrlParam;
+
+/// Represents query parser used for the main query (defType)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueryParser {
+    Lucene,
+    DisMax,
+    EDisMax
+}
+
+impl ToUrlParam for QueryParser {
+    fn to_url_param(&self) -> String {
+        match *self {
+            QueryParser::Lucene => "lucene".to_string(),
+            QueryParser::DisMax => "dismax".to_string(),
+            QueryParser::EDisMax => "edismax".to_string()
+        }
+    }
+}
+
+/// A utility struct to hold a field with optional boost, for example `title^2`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldBoost {
+    pub field: String,
+    pub boost: Option<f64>
+}
+
+impl ToUrlParam for FieldBoost {
+    fn to_url_param(&self) -> String {
+        match self.boost {
+            Some(boost) => format!("{}^{}", self.field, boost),
+            None => self.field.clone()
+        }
+    }
+}
+
+/// Represents minimum number of optional clauses that must match (mm)
+#[derive(Clone, Debug, PartialEq)]
+pub enum MinimumMatch {
+    /// Number of clauses, negative means number of clauses that may be missing
+    Count(i32),
+    /// Percentage of clauses, negative means percentage that may be missing
+    Percent(i32),
+    /// Conditional specification, each pair applies when the number of clauses
+    /// is greater than the given number, for example `2<-25% 9<-3`
+    Conditional(Vec<(u32, MinimumMatch)>)
+}
+
+impl ToUrlParam for MinimumMatch {
+    fn to_url_param(&self) -> String {
+        match *self {
+            MinimumMatch::Count(c) => c.to_string(),
+            MinimumMatch::Percent(p) => format!("{}%", p),
+            MinimumMatch::Conditional(ref conditions) => {
+                let specs: Vec<String> = conditions.iter()
+                                                   .map(|&(clauses, ref mm)| format!("{}<{}", clauses, mm.to_url_param()))
+                                                   .collect();
+                specs.join(" ")
+            }
+        }
+    }
+}
+
+/// Represents DisMax and eDisMax query parser parameters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DisMax {
+    pub qf: Vec<FieldBoost>,
+    pub pf: Vec<FieldBoost>,
+    pub pf2: Vec<FieldBoost>,
+    pub pf3: Vec<FieldBoost>,
+    pub ps: Option<u32>,
+    pub qs: Option<u32>,
+    pub mm: Option<MinimumMatch>,
+    pub tie: Option<f64>,
+    pub bq: Vec<String>,
+    pub bf: Vec<String>,
+    pub boost: Vec<String>,
+    pub uf: Option<String>,
+    pub lowercase_operators: Option<bool>,
+    pub stopwords: Option<bool>
+}
+
+impl DisMax {
+    /// Creates empty DisMax parameters
+    pub fn new() -> DisMax {
+        DisMax::default()
+    }
+
+    /// Adds query field with optional boost (qf)
+    pub fn add_qf(&self, field: &str, boost: Option<f64>) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.qf.push(FieldBoost{field: field.to_string(), boost: boost});
+        dismax
+    }
+
+    /// Adds phrase field with optional boost (pf)
+    pub fn add_pf(&self, field: &str, boost: Option<f64>) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.pf.push(FieldBoost{field: field.to_string(), boost: boost});
+        dismax
+    }
+
+    /// Adds bigram phrase field with optional boost (pf2), eDisMax only
+    pub fn add_pf2(&self, field: &str, boost: Option<f64>) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.pf2.push(FieldBoost{field: field.to_string(), boost: boost});
+        dismax
+    }
+
+    /// Adds trigram phrase field with optional boost (pf3), eDisMax only
+    pub fn add_pf3(&self, field: &str, boost: Option<f64>) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.pf3.push(FieldBoost{field: field.to_string(), boost: boost});
+        dismax
+    }
+
+    /// Sets phrase slop for phrase fields (ps)
+    pub fn ps(&self, ps: u32) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.ps = Some(ps);
+        dismax
+    }
+
+    /// Sets slop for phrases explicitly present in the query (qs)
+    pub fn qs(&self, qs: u32) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.qs = Some(qs);
+        dismax
+    }
+
+    /// Sets minimum should match (mm)
+    pub fn mm(&self, mm: MinimumMatch) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.mm = Some(mm);
+        dismax
+    }
+
+    /// Sets tie breaker between fields (tie), between 0.0 and 1.0
+    pub fn tie(&self, tie: f64) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.tie = Some(tie);
+        dismax
+    }
+
+    /// Adds boost query (bq)
+    pub fn add_bq(&self, bq: &str) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.bq.push(bq.to_string());
+        dismax
+    }
+
+    /// Adds additive boost function (bf)
+    pub fn add_bf(&self, bf: &str) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.bf.push(bf.to_string());
+        dismax
+    }
+
+    /// Adds multiplicative boost function (boost), eDisMax only
+    pub fn add_boost(&self, boost: &str) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.boost.push(boost.to_string());
+        dismax
+    }
+
+    /// Sets fields users are allowed to query (uf), eDisMax only, for example `title body -secret`
+    pub fn uf(&self, uf: &str) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.uf = Some(uf.to_string());
+        dismax
+    }
+
+    /// Sets whether lowercase and/or are treated as operators (lowercaseOperators), eDisMax only
+    pub fn lowercase_operators(&self, lowercase_operators: bool) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.lowercase_operators = Some(lowercase_operators);
+        dismax
+    }
+
+    /// Sets whether the stop filter of the query analyzer is respected (stopwords), eDisMax only
+    pub fn stopwords(&self, stopwords: bool) -> DisMax {
+        let mut dismax = self.clone();
+        dismax.stopwords = Some(stopwords);
+        dismax
+    }
+
+    /// Converts these parameters to URL pairs
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = Vec::new();
+        let fields = [("qf", &self.qf), ("pf", &self.pf), ("pf2", &self.pf2), ("pf3", &self.pf3)];
+        for &(param, field_boosts) in fields.iter() {
+            if !field_boosts.is_empty() {
+                let fmt_fields: Vec<String> = field_boosts.iter().map(|x| x.to_url_param()).collect();
+                vec.push((param.to_string(), fmt_fields.join(" ")));
+            }
+        }
+        if let Some(ps) = self.ps {
+            vec.push(("ps".to_string(), ps.to_string()));
+        }
+        if let Some(qs) = self.qs {
+            vec.push(("qs".to_string(), qs.to_string()));
+        }
+        if let Some(ref mm) = self.mm {
+            vec.push(("mm".to_string(), mm.to_url_param()));
+        }
+        if let Some(tie) = self.tie {
+            vec.push(("tie".to_string(), tie.to_string()));
+        }
+        vec.extend(self.bq.iter().map(|x| ("bq".to_string(), x.clone())));
+        vec.extend(self.bf.iter().map(|x| ("bf".to_string(), x.clone())));
+        vec.extend(self.boost.iter().map(|x| ("boost".to_string(), x.clone())));
+        if let Some(ref uf) = self.uf {
+            vec.push(("uf".to_string(), uf.clone()));
+        }
+        if let Some(lowercase_operators) = self.lowercase_operators {
+            vec.push(("lowercaseOperators".to_string(), lowercase_operators.to_string()));
+        }
+        if let Some(stopwords) = self.stopwords {
+            vec.push(("stopwords".to_string(), stopwords.to_string()));
+        }
+        vec
+    }
+}
@@ -1,12 +1,22 @@
+use std::borrow::Cow;
 use rustc_serialize::{Encodable, Encoder};
+use rustc_serialize::base64::{ToBase64, FromBase64, STANDARD};
+use time::{self, Tm};
+use query::ToUrlParam;
+use geo::GeoPoint;
+use response::{SolrError, SolrErrorKind};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SolrValue {
     I64(i64),
     U64(u64),
     F64(f64),
     String(String),
     Boolean(bool),
+    /// Values of a multi-valued field
+    Array(Vec<SolrValue>),
+    /// Labelled nested child documents
+    Documents(Vec<SolrDocument>),
     Null
 }
 impl Encodable for SolrValue {
@@ -17,46 +27,325 @@ impl Encodable for SolrValue {
             SolrValue::F64(v) => v.encode(e),
             SolrValue::String(ref v) => v.encode(e),
             SolrValue::Boolean(v) => v.encode(e),
+            SolrValue::Array(ref v) => v.encode(e),
+            SolrValue::Documents(ref v) => v.encode(e),
             SolrValue::Null => "null".encode(e)
         }
     }
 }
+/// Expected `_version_` of a document, for optimistic concurrency
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DocumentVersion {
+    /// Document is updated regardless of its version
+    Any,
+    /// Document must already exist
+    MustExist,
+    /// Document must not exist yet
+    MustNotExist,
+    /// Indexed document must have exactly this version
+    Exactly(i64)
+}
+
+impl DocumentVersion {
+    /// Value of the `_version_` field
+    pub fn value(&self) -> i64 {
+        match *self {
+            DocumentVersion::Any => 0,
+            DocumentVersion::MustExist => 1,
+            DocumentVersion::MustNotExist => -1,
+            DocumentVersion::Exactly(v) => v
+        }
+    }
+}
+
 /// SolrDocument field
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SolrField {
     pub name: String,
     pub value: SolrValue
 }
 
 /// SolrDocument to be used to either index or query.
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SolrDocument {
     /// Collection of document fields
-    pub fields: Vec<SolrField>
+    pub fields: Vec<SolrField>,
+    /// Anonymous nested child documents (_childDocuments_)
+    pub children: Vec<SolrDocument>
 }
 
 impl SolrDocument {
     /// Creates new empty SolrDocument
     pub fn new() -> SolrDocument {
         let fields: Vec<SolrField> = Vec::with_capacity(10);
-        SolrDocument{fields: fields}
+        SolrDocument{fields: fields, children: Vec::new()}
     }
 
     /// Adds a field to the document
     pub fn add_field(&mut self, name: &str, value: &str) {
         self.fields.push(SolrField{name: name.to_string(), value: SolrValue::String(value.to_string())});
     }
+
+    /// Adds a signed integer field
+    pub fn add_i64(&mut self, name: &str, value: i64) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::I64(value)});
+    }
+
+    /// Adds an unsigned integer field
+    pub fn add_u64(&mut self, name: &str, value: u64) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::U64(value)});
+    }
+
+    /// Adds a floating point field
+    pub fn add_f64(&mut self, name: &str, value: f64) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::F64(value)});
+    }
+
+    /// Adds a boolean field
+    pub fn add_bool(&mut self, name: &str, value: bool) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::Boolean(value)});
+    }
+
+    /// Adds a date field, formatted in UTC as Solr expects, for example 2015-05-01T12:30:00Z
+    pub fn add_date(&mut self, name: &str, value: &Tm) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::String(format_date(value))});
+    }
+
+    /// Adds a binary field, encoded as base64
+    pub fn add_binary(&mut self, name: &str, value: &[u8]) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::String(value.to_base64(STANDARD))});
+    }
+
+    /// Adds a multi-valued field
+    pub fn add_values(&mut self, name: &str, values: &[&str]) {
+        let values = values.iter().map(|x| SolrValue::String(x.to_string())).collect();
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::Array(values)});
+    }
+
+    /// Adds anonymous nested child document, sent as _childDocuments_
+    pub fn add_child(&mut self, child: SolrDocument) {
+        self.children.push(child);
+    }
+
+    /// Adds nested child documents under a relationship field, for nested schema (Solr 8+)
+    pub fn add_children(&mut self, name: &str, children: Vec<SolrDocument>) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::Documents(children)});
+    }
+
+    /// Adds a point field, formatted as `lat,lon`
+    pub fn add_point(&mut self, name: &str, point: GeoPoint) {
+        self.fields.push(SolrField{name: name.to_string(), value: SolrValue::String(point.to_url_param())});
+    }
+
+    /// Finds point value of the first field with a given name
+    pub fn get_point(&self, name: &str) -> Option<GeoPoint> {
+        match self.get(name) {
+            Some(&SolrValue::String(ref s)) => GeoPoint::parse(s),
+            _ => None
+        }
+    }
+
+    /// Sets expected `_version_`, replacing the one returned by a query.
+    /// On mismatch Solr rejects the document with a version conflict error.
+    pub fn set_version(&mut self, version: DocumentVersion) {
+        self.fields.retain(|f| f.name != "_version_");
+        self.fields.push(SolrField{name: "_version_".to_string(), value: SolrValue::I64(version.value())});
+    }
+
+    /// Finds `_version_` of a document returned by a query
+    pub fn get_version(&self) -> Result<i64, SolrError> {
+        self.get_i64("_version_")
+    }
+
+    /// Finds value of the first field with a given name
+    pub fn get(&self, name: &str) -> Option<&SolrValue> {
+        self.fields.iter().find(|f| f.name == name).map(|f| &f.value)
+    }
+
+    /// Finds string value of the first field with a given name
+    pub fn get_str(&self, name: &str) -> Result<&str, SolrError> {
+        match try!(self.get_required(name)) {
+            &SolrValue::String(ref s) => Ok(s),
+            v => Err(type_mismatch(name, "string", v))
+        }
+    }
+
+    /// Finds signed integer value of the first field with a given name
+    pub fn get_i64(&self, name: &str) -> Result<i64, SolrError> {
+        match try!(self.get_required(name)) {
+            &SolrValue::I64(i) => Ok(i),
+            &SolrValue::U64(u) if u <= i64::max_value() as u64 => Ok(u as i64),
+            v => Err(type_mismatch(name, "i64", v))
+        }
+    }
+
+    /// Finds unsigned integer value of the first field with a given name
+    pub fn get_u64(&self, name: &str) -> Result<u64, SolrError> {
+        match try!(self.get_required(name)) {
+            &SolrValue::U64(u) => Ok(u),
+            &SolrValue::I64(i) if i >= 0 => Ok(i as u64),
+            v => Err(type_mismatch(name, "u64", v))
+        }
+    }
+
+    /// Finds floating point value of the first field with a given name, integers are converted
+    pub fn get_f64(&self, name: &str) -> Result<f64, SolrError> {
+        match try!(self.get_required(name)) {
+            &SolrValue::F64(f) => Ok(f),
+            &SolrValue::I64(i) => Ok(i as f64),
+            &SolrValue::U64(u) => Ok(u as f64),
+            v => Err(type_mismatch(name, "f64", v))
+        }
+    }
+
+    /// Finds boolean value of the first field with a given name
+    pub fn get_bool(&self, name: &str) -> Result<bool, SolrError> {
+        match try!(self.get_required(name)) {
+            &SolrValue::Boolean(b) => Ok(b),
+            v => Err(type_mismatch(name, "bool", v))
+        }
+    }
+
+    /// Finds date value of the first field with a given name, the returned time is in UTC
+    pub fn get_date(&self, name: &str) -> Result<Tm, SolrError> {
+        let value = try!(self.get_str(name));
+        parse_date(value).map_err(|e| field_error(format!("Field {} is not a date: {}", name, e)))
+    }
+
+    /// Finds base64 encoded binary value of the first field with a given name
+    pub fn get_binary(&self, name: &str) -> Result<Vec<u8>, SolrError> {
+        let value = try!(self.get_str(name));
+        value.from_base64().map_err(|e| field_error(format!("Field {} is not base64 binary: {}", name, e)))
+    }
+
+    fn get_required(&self, name: &str) -> Result<&SolrValue, SolrError> {
+        match self.get(name) {
+            Some(v) => Ok(v),
+            None => Err(field_error(format!("Field {} not found", name)))
+        }
+    }
+
+    /// Finds all values of fields with a given name, multi-valued fields are flattened
+    pub fn get_values(&self, name: &str) -> Vec<&SolrValue> {
+        let mut values = Vec::new();
+        for field in self.fields.iter().filter(|f| f.name == name) {
+            match field.value {
+                SolrValue::Array(ref v) => values.extend(v.iter()),
+                ref v => values.push(v)
+            }
+        }
+        values
+    }
+
+    /// Finds value of a field, values of a field repeated under the same name are merged into an array
+    pub fn get_merged(&self, name: &str) -> Option<Cow<SolrValue>> {
+        if self.fields.iter().filter(|f| f.name == name).count() > 1 {
+            Some(Cow::Owned(SolrValue::Array(self.get_values(name).into_iter().cloned().collect())))
+        } else {
+            self.get(name).map(Cow::Borrowed)
+        }
+    }
+
+    /// Names of the fields in order of their first occurrence
+    pub fn field_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::with_capacity(self.fields.len());
+        for field in self.fields.iter() {
+            if !names.contains(&&field.name[..]) {
+                names.push(&field.name);
+            }
+        }
+        names
+    }
 }
 
+// Fields added several times under the same name are sent as a single JSON array
 impl Encodable for SolrDocument {
     fn encode<E: Encoder>(&self, s: &mut E) -> Result<(), E::Error> {
         let mut i = 0usize;
-        s.emit_struct("SolrDocument", self.fields.len(), |e| {
-            for field in self.fields.iter() {
-                try!(e.emit_struct_field(&field.name, i, |e| field.value.encode(e)));
+        let names = self.field_names();
+        let len = if self.children.is_empty() { names.len() } else { names.len() + 1 };
+        s.emit_struct("SolrDocument", len, |e| {
+            for name in names.iter() {
+                let fields: Vec<&SolrField> = self.fields.iter().filter(|f| f.name == *name).collect();
+                if fields.len() == 1 {
+                    try!(e.emit_struct_field(name, i, |e| fields[0].value.encode(e)));
+                } else {
+                    try!(e.emit_struct_field(name, i, |e| encode_merged(&fields, e)));
+                }
                 i = i + 1;
             }
+            if !self.children.is_empty() {
+                try!(e.emit_struct_field("_childDocuments_", i, |e| self.children.encode(e)));
+            }
             Ok(())
         })
     }
 }
+
+fn encode_merged<E: Encoder>(fields: &[&SolrField], e: &mut E) -> Result<(), E::Error> {
+    let len = fields.iter().map(|f| match f.value {
+        SolrValue::Array(ref v) => v.len(),
+        SolrValue::Documents(ref v) => v.len(),
+        _ => 1
+    }).sum();
+    e.emit_seq(len, |e| {
+        let mut i = 0usize;
+        for field in fields.iter() {
+            match field.value {
+                SolrValue::Array(ref values) => for v in values.iter() {
+                    try!(e.emit_seq_elt(i, |e| v.encode(e)));
+                    i = i + 1;
+                },
+                SolrValue::Documents(ref docs) => for doc in docs.iter() {
+                    try!(e.emit_seq_elt(i, |e| doc.encode(e)));
+                    i = i + 1;
+                },
+                ref v => {
+                    try!(e.emit_seq_elt(i, |e| v.encode(e)));
+                    i = i + 1;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+fn field_error(message: String) -> SolrError {
+    SolrError{status: 0, time: 0, message: message, kind: SolrErrorKind::Other}
+}
+
+fn type_mismatch(name: &str, expected: &str, value: &SolrValue) -> SolrError {
+    field_error(format!("Field {} is not {}: {:?}", name, expected, value))
+}
+
+/// Formats time in UTC as Solr date, with milliseconds only when not zero
+pub fn format_date(tm: &Tm) -> String {
+    let utc = time::at_utc(tm.to_timespec());
+    let mut date = time::strftime("%Y-%m-%dT%H:%M:%S", &utc).unwrap();
+    if utc.tm_nsec / 1_000_000 != 0 {
+        date.push_str(&format!(".{:03}", utc.tm_nsec / 1_000_000));
+    }
+    date.push('Z');
+    date
+}
+
+/// Parses Solr date, for example 2015-05-01T12:30:00Z or 2015-05-01T12:30:00.250Z
+pub fn parse_date(value: &str) -> Result<Tm, String> {
+    if !value.ends_with('Z') {
+        return Err(format!("{} is not in UTC", value));
+    }
+    let value = &value[..value.len() - 1];
+    let (seconds, fraction) = match value.find('.') {
+        Some(dot) => (&value[..dot], &value[dot + 1..]),
+        None => (value, "")
+    };
+    let mut tm = try!(time::strptime(seconds, "%Y-%m-%dT%H:%M:%S").map_err(|e| e.to_string()));
+    if !fraction.is_empty() {
+        if fraction.len() > 9 || !fraction.chars().all(|c| c.is_digit(10)) {
+            return Err(format!("{} has invalid fraction of second", value));
+        }
+        let padded = format!("{:0<9}", fraction);
+        tm.tm_nsec = padded.parse().unwrap();
+    }
+    Ok(tm)
+}
@@ -0,0 +1,405 @@
+use query::ToUrlParam;
+use query_dsl::format_param_value;
+
+/// Represents ordering of facet terms (facet.sort)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FacetSort {
+    /// Highest count first
+    Count,
+    /// Lexicographic order of indexed terms
+    Index
+}
+
+impl ToUrlParam for FacetSort {
+    fn to_url_param(&self) -> String {
+        match *self {
+            FacetSort::Count => "count".to_string(),
+            FacetSort::Index => "index".to_string()
+        }
+    }
+}
+
+/// Field faceting options.
+/// Used both for global defaults (facet.*) and per-field overrides (f.<field>.facet.*).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FacetOptions {
+    pub limit: Option<i32>,
+    pub mincount: Option<u32>,
+    pub sort: Option<FacetSort>,
+    pub prefix: Option<String>,
+    pub missing: Option<bool>
+}
+
+impl FacetOptions {
+    /// Returns true when none of the options is set
+    pub fn is_empty(&self) -> bool {
+        *self == FacetOptions::default()
+    }
+
+    /// Converts options to URL pairs, each parameter name prefixed with `param_prefix`,
+    /// which is either empty or `f.<field>.`
+    pub fn to_pairs(&self, param_prefix: &str) -> Vec<(String, String)> {
+        let mut vec = Vec::new();
+        if let Some(limit) = self.limit {
+            vec.push((format!("{}facet.limit", param_prefix), limit.to_string()));
+        }
+        if let Some(mincount) = self.mincount {
+            vec.push((format!("{}facet.mincount", param_prefix), mincount.to_string()));
+        }
+        if let Some(ref sort) = self.sort {
+            vec.push((format!("{}facet.sort", param_prefix), sort.to_url_param()));
+        }
+        if let Some(ref prefix) = self.prefix {
+            vec.push((format!("{}facet.prefix", param_prefix), prefix.clone()));
+        }
+        if let Some(missing) = self.missing {
+            vec.push((format!("{}facet.missing", param_prefix), missing.to_string()));
+        }
+        vec
+    }
+}
+
+/// Represents a field facet (facet.field) with optional per-field options.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FacetField {
+    pub field: String,
+    pub options: FacetOptions,
+    /// Tags of filters to exclude when counting ({!ex=...})
+    pub exclude: Vec<String>,
+    /// Label used in the response instead of the field name ({!key=...})
+    pub key: Option<String>
+}
+
+impl FacetField {
+    /// Creates a new facet on the given field
+    pub fn new(field: &str) -> FacetField {
+        FacetField{field: field.to_string(), options: FacetOptions::default(), exclude: Vec::new(), key: None}
+    }
+
+    /// Sets maximum number of terms returned for this field (f.<field>.facet.limit)
+    pub fn limit(&self, limit: i32) -> FacetField {
+        let mut facet = self.clone();
+        facet.options.limit = Some(limit);
+        facet
+    }
+
+    /// Sets minimum count for a term to be returned (f.<field>.facet.mincount)
+    pub fn mincount(&self, mincount: u32) -> FacetField {
+        let mut facet = self.clone();
+        facet.options.mincount = Some(mincount);
+        facet
+    }
+
+    /// Sets terms ordering (f.<field>.facet.sort)
+    pub fn sort(&self, sort: FacetSort) -> FacetField {
+        let mut facet = self.clone();
+        facet.options.sort = Some(sort);
+        facet
+    }
+
+    /// Limits terms to those starting with prefix (f.<field>.facet.prefix)
+    pub fn prefix(&self, prefix: &str) -> FacetField {
+        let mut facet = self.clone();
+        facet.options.prefix = Some(prefix.to_string());
+        facet
+    }
+
+    /// Enables counting of documents without a value (f.<field>.facet.missing)
+    pub fn missing(&self, missing: bool) -> FacetField {
+        let mut facet = self.clone();
+        facet.options.missing = Some(missing);
+        facet
+    }
+
+    /// Excludes filters tagged with tag when counting, used for multi-select faceting
+    pub fn exclude(&self, tag: &str) -> FacetField {
+        let mut facet = self.clone();
+        facet.exclude.push(tag.to_string());
+        facet
+    }
+
+    /// Sets label used in the response instead of the field name
+    pub fn key(&self, key: &str) -> FacetField {
+        let mut facet = self.clone();
+        facet.key = Some(key.to_string());
+        facet
+    }
+
+    /// Converts this facet to URL pairs: facet.field followed by per-field options
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("facet.field".to_string(), with_local_params(&self.field, &self.exclude, &self.key)));
+        vec.extend(self.options.to_pairs(&format!("f.{}.", self.field)));
+        vec
+    }
+}
+
+/// Represents additional range facet counts (facet.range.other)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FacetRangeOther {
+    /// Documents with values lower than the first range
+    Before,
+    /// Documents with values greater than the last range
+    After,
+    /// Documents with values between start and end
+    Between,
+    None,
+    All
+}
+
+impl ToUrlParam for FacetRangeOther {
+    fn to_url_param(&self) -> String {
+        match *self {
+            FacetRangeOther::Before => "before".to_string(),
+            FacetRangeOther::After => "after".to_string(),
+            FacetRangeOther::Between => "between".to_string(),
+            FacetRangeOther::None => "none".to_string(),
+            FacetRangeOther::All => "all".to_string()
+        }
+    }
+}
+
+/// Represents range bounds inclusion (facet.range.include)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FacetRangeInclude {
+    Lower,
+    Upper,
+    Edge,
+    Outer,
+    All
+}
+
+impl ToUrlParam for FacetRangeInclude {
+    fn to_url_param(&self) -> String {
+        match *self {
+            FacetRangeInclude::Lower => "lower".to_string(),
+            FacetRangeInclude::Upper => "upper".to_string(),
+            FacetRangeInclude::Edge => "edge".to_string(),
+            FacetRangeInclude::Outer => "outer".to_string(),
+            FacetRangeInclude::All => "all".to_string()
+        }
+    }
+}
+
+/// Represents a range facet (facet.range) on a numeric or date field.
+/// Bounds and gap are passed as Solr values, for example `0`, `100`, `10`
+/// or `NOW/DAY-30DAYS`, `NOW/DAY`, `+1DAY`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FacetRange {
+    pub field: String,
+    pub start: String,
+    pub end: String,
+    pub gap: String,
+    pub hardend: Option<bool>,
+    pub other: Option<Vec<FacetRangeOther>>,
+    pub include: Option<Vec<FacetRangeInclude>>,
+    /// Tags of filters to exclude when counting ({!ex=...})
+    pub exclude: Vec<String>,
+    /// Label used in the response instead of the field name ({!key=...})
+    pub key: Option<String>
+}
+
+impl FacetRange {
+    /// Creates a new range facet
+    pub fn new(field: &str, start: &str, end: &str, gap: &str) -> FacetRange {
+        FacetRange{field: field.to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            gap: gap.to_string(),
+            hardend: None,
+            other: None,
+            include: None,
+            exclude: Vec::new(),
+            key: None}
+    }
+
+    /// Creates a new range facet on a numeric field
+    pub fn numeric(field: &str, start: f64, end: f64, gap: f64) -> FacetRange {
+        FacetRange::new(field, &start.to_string(), &end.to_string(), &gap.to_string())
+    }
+
+    /// Sets whether the last range is truncated at end (facet.range.hardend)
+    pub fn hardend(&self, hardend: bool) -> FacetRange {
+        let mut facet = self.clone();
+        facet.hardend = Some(hardend);
+        facet
+    }
+
+    /// Adds additional counts to compute (facet.range.other)
+    pub fn add_other(&self, other: FacetRangeOther) -> FacetRange {
+        let mut facet = self.clone();
+        facet.other = match facet.other {
+            Some(mut o) => {
+                o.push(other);
+                Some(o)
+            },
+            None => Some(vec!(other))
+        };
+        facet
+    }
+
+    /// Adds bounds inclusion rule (facet.range.include)
+    pub fn add_include(&self, include: FacetRangeInclude) -> FacetRange {
+        let mut facet = self.clone();
+        facet.include = match facet.include {
+            Some(mut i) => {
+                i.push(include);
+                Some(i)
+            },
+            None => Some(vec!(include))
+        };
+        facet
+    }
+
+    /// Excludes filters tagged with tag when counting, used for multi-select faceting
+    pub fn exclude(&self, tag: &str) -> FacetRange {
+        let mut facet = self.clone();
+        facet.exclude.push(tag.to_string());
+        facet
+    }
+
+    /// Sets label used in the response instead of the field name
+    pub fn key(&self, key: &str) -> FacetRange {
+        let mut facet = self.clone();
+        facet.key = Some(key.to_string());
+        facet
+    }
+
+    /// Converts this facet to URL pairs: facet.range followed by per-field parameters
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let param_prefix = format!("f.{}.facet.range", self.field);
+        let mut vec = vec!(("facet.range".to_string(), with_local_params(&self.field, &self.exclude, &self.key)),
+                           (format!("{}.start", param_prefix), self.start.clone()),
+                           (format!("{}.end", param_prefix), self.end.clone()),
+                           (format!("{}.gap", param_prefix), self.gap.clone()));
+        if let Some(hardend) = self.hardend {
+            vec.push((format!("{}.hardend", param_prefix), hardend.to_string()));
+        }
+        if let Some(ref other) = self.other {
+            vec.extend(other.iter().map(|x| (format!("{}.other", param_prefix), x.to_url_param())));
+        }
+        if let Some(ref include) = self.include {
+            vec.extend(include.iter().map(|x| (format!("{}.include", param_prefix), x.to_url_param())));
+        }
+        vec
+    }
+}
+
+/// Represents an interval facet (facet.interval) with its interval sets
+#[derive(Clone, Debug, PartialEq)]
+pub struct FacetInterval {
+    pub field: String,
+    /// Interval sets in Solr syntax, for example `[0,10)` or `{!key=cheap}[*,10)`
+    pub sets: Vec<String>,
+    /// Tags of filters to exclude when counting ({!ex=...})
+    pub exclude: Vec<String>,
+    /// Label used in the response instead of the field name ({!key=...})
+    pub key: Option<String>
+}
+
+impl FacetInterval {
+    /// Creates a new interval facet without any intervals
+    pub fn new(field: &str) -> FacetInterval {
+        FacetInterval{field: field.to_string(), sets: Vec::new(), exclude: Vec::new(), key: None}
+    }
+
+    /// Adds interval set in Solr syntax (f.<field>.facet.interval.set)
+    pub fn add_set(&self, set: &str) -> FacetInterval {
+        let mut facet = self.clone();
+        facet.sets.push(set.to_string());
+        facet
+    }
+
+    /// Adds interval between start and end, `None` meaning unbounded
+    pub fn add_interval(&self, start: Option<&str>, end: Option<&str>,
+                        include_start: bool, include_end: bool) -> FacetInterval {
+        self.add_set(&format_interval(start, end, include_start, include_end))
+    }
+
+    /// Adds interval labelled with key, which is used in the response instead of the interval itself
+    pub fn add_keyed_interval(&self, key: &str, start: Option<&str>, end: Option<&str>,
+                              include_start: bool, include_end: bool) -> FacetInterval {
+        self.add_set(&format!("{{!key={}}}{}", format_param_value(key), format_interval(start, end, include_start, include_end)))
+    }
+
+    /// Excludes filters tagged with tag when counting, used for multi-select faceting
+    pub fn exclude(&self, tag: &str) -> FacetInterval {
+        let mut facet = self.clone();
+        facet.exclude.push(tag.to_string());
+        facet
+    }
+
+    /// Sets label used in the response instead of the field name
+    pub fn key(&self, key: &str) -> FacetInterval {
+        let mut facet = self.clone();
+        facet.key = Some(key.to_string());
+        facet
+    }
+
+    /// Converts this facet to URL pairs: facet.interval followed by interval sets
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("facet.interval".to_string(), with_local_params(&self.field, &self.exclude, &self.key)));
+        let param = format!("f.{}.facet.interval.set", self.field);
+        vec.extend(self.sets.iter().map(|x| (param.clone(), x.clone())));
+        vec
+    }
+}
+
+fn format_interval(start: Option<&str>, end: Option<&str>, include_start: bool, include_end: bool) -> String {
+    format!("{}{},{}{}",
+            if include_start { "[" } else { "(" },
+            start.unwrap_or("*"),
+            end.unwrap_or("*"),
+            if include_end { "]" } else { ")" })
+}
+
+/// Represents a pivot (decision tree) facet (facet.pivot) over two or more fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct FacetPivot {
+    pub fields: Vec<String>,
+    /// Tags of filters to exclude when counting ({!ex=...})
+    pub exclude: Vec<String>,
+    /// Label used in the response instead of the field list ({!key=...})
+    pub key: Option<String>
+}
+
+impl FacetPivot {
+    /// Creates a new pivot facet, fields are listed from the top level down
+    pub fn new(fields: &[&str]) -> FacetPivot {
+        FacetPivot{fields: fields.iter().map(|x| x.to_string()).collect(), exclude: Vec::new(), key: None}
+    }
+
+    /// Excludes filters tagged with tag when counting, used for multi-select faceting
+    pub fn exclude(&self, tag: &str) -> FacetPivot {
+        let mut facet = self.clone();
+        facet.exclude.push(tag.to_string());
+        facet
+    }
+
+    /// Sets label used in the response instead of the field list
+    pub fn key(&self, key: &str) -> FacetPivot {
+        let mut facet = self.clone();
+        facet.key = Some(key.to_string());
+        facet
+    }
+
+    /// Converts this facet to URL pairs
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        vec!(("facet.pivot".to_string(), with_local_params(&self.fields.join(","), &self.exclude, &self.key)))
+    }
+}
+
+/// Prefixes facet parameter value with {!ex=... key=...} local params, when any is set
+fn with_local_params(value: &str, exclude: &[String], key: &Option<String>) -> String {
+    let mut params = Vec::new();
+    if !exclude.is_empty() {
+        params.push(format!("ex={}", format_param_value(&exclude.join(","))));
+    }
+    if let Some(ref k) = *key {
+        params.push(format!("key={}", format_param_value(k)));
+    }
+    if params.is_empty() {
+        value.to_string()
+    } else {
+        format!("{{!{}}}{}", params.join(" "), value)
+    }
+}
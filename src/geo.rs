@@ -0,0 +1,102 @@
+use query::ToUrlParam;
+use query_dsl::{Query, LocalParams};
+
+/// Represents a point on Earth, in degrees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64
+}
+
+impl GeoPoint {
+    /// Creates a new point from latitude and longitude
+    pub fn new(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint{lat: lat, lon: lon}
+    }
+
+    /// Parses point in Solr `lat,lon` format
+    pub fn parse(value: &str) -> Option<GeoPoint> {
+        let mut parts = value.split(',');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(lat), Some(lon), None) => match (lat.trim().parse(), lon.trim().parse()) {
+                (Ok(lat), Ok(lon)) => Some(GeoPoint{lat: lat, lon: lon}),
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    /// Filters documents within distance in kilometers from this point ({!geofilt})
+    pub fn geofilt(&self, sfield: &str, distance: f64) -> Query {
+        self.spatial_filter("geofilt", sfield, distance)
+    }
+
+    /// Filters documents within bounding box of a circle around this point ({!bbox}),
+    /// which is faster but less precise than geofilt
+    pub fn bbox(&self, sfield: &str, distance: f64) -> Query {
+        self.spatial_filter("bbox", sfield, distance)
+    }
+
+    /// Function computing distance in kilometers from this point,
+    /// to be used in sort, for example `geodist(store,45.15,-93.85)`
+    pub fn geodist(&self, sfield: &str) -> String {
+        format!("geodist({},{},{})", sfield, self.lat, self.lon)
+    }
+
+    /// Pseudo-field returning distance in kilometers from this point under alias,
+    /// to be used in fl, for example `dist:geodist(store,45.15,-93.85)`
+    pub fn distance_field(&self, alias: &str, sfield: &str) -> String {
+        format!("{}:{}", alias, self.geodist(sfield))
+    }
+
+    /// Formats points as WKT polygon, closing the ring when needed
+    pub fn polygon_wkt(points: &[GeoPoint]) -> String {
+        let mut coords: Vec<String> = points.iter().map(|p| format!("{} {}", p.lon, p.lat)).collect();
+        if points.len() > 1 && points.first() != points.last() {
+            coords.push(format!("{} {}", points[0].lon, points[0].lat));
+        }
+        format!("POLYGON(({}))", coords.join(", "))
+    }
+
+    fn spatial_filter(&self, parser: &str, sfield: &str, distance: f64) -> Query {
+        let local_params = LocalParams::new(Some(parser))
+            .param("sfield", sfield)
+            .param("pt", &self.to_url_param())
+            .param("d", &distance.to_string());
+        Query::local_params(local_params, None)
+    }
+}
+
+impl ToUrlParam for GeoPoint {
+    fn to_url_param(&self) -> String {
+        format!("{},{}", self.lat, self.lon)
+    }
+}
+
+/// Represents spatial relation of indexed shapes to the query shape, for RPT fields
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpatialPredicate {
+    Intersects,
+    IsWithin,
+    Contains,
+    IsDisjointTo
+}
+
+impl ToUrlParam for SpatialPredicate {
+    fn to_url_param(&self) -> String {
+        match *self {
+            SpatialPredicate::Intersects => "Intersects".to_string(),
+            SpatialPredicate::IsWithin => "IsWithin".to_string(),
+            SpatialPredicate::Contains => "Contains".to_string(),
+            SpatialPredicate::IsDisjointTo => "IsDisjointTo".to_string()
+        }
+    }
+}
+
+impl SpatialPredicate {
+    /// Filters documents whose shapes in field are in this relation to WKT shape,
+    /// for example `geo:"Intersects(POLYGON((...)))"`
+    pub fn filter(&self, field: &str, wkt: &str) -> Query {
+        Query::Phrase(Some(field.to_string()), format!("{}({})", self.to_url_param(), wkt))
+    }
+}
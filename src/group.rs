@@ -0,0 +1,289 @@
+use query::{ToUrlParam, SortClause, SortOrder};
+
+/// Represents grouped response format (group.format)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupFormat {
+    /// Documents are returned per group
+    Grouped,
+    /// Documents of all groups are returned in a single flat list
+    Simple
+}
+
+impl ToUrlParam for GroupFormat {
+    fn to_url_param(&self) -> String {
+        match *self {
+            GroupFormat::Grouped => "grouped".to_string(),
+            GroupFormat::Simple => "simple".to_string()
+        }
+    }
+}
+
+/// Represents result grouping options (group.*).
+#[derive(Clone, Default)]
+pub struct Grouping {
+    pub fields: Vec<String>,
+    pub queries: Vec<String>,
+    pub funcs: Vec<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub sorts: Option<Vec<SortClause>>,
+    pub ngroups: Option<bool>,
+    pub main: Option<bool>,
+    pub format: Option<GroupFormat>
+}
+
+impl Grouping {
+    /// Creates grouping options without any group commands
+    pub fn new() -> Grouping {
+        Grouping::default()
+    }
+
+    /// Adds grouping by unique values of a field (group.field)
+    pub fn add_field(&self, field: &str) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.fields.push(field.to_string());
+        grouping
+    }
+
+    /// Adds a single group of documents matching query (group.query)
+    pub fn add_query(&self, query: &str) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.queries.push(query.to_string());
+        grouping
+    }
+
+    /// Adds grouping by unique values of a function query (group.func)
+    pub fn add_func(&self, func: &str) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.funcs.push(func.to_string());
+        grouping
+    }
+
+    /// Sets number of documents returned per group (group.limit)
+    pub fn limit(&self, limit: u32) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.limit = Some(limit);
+        grouping
+    }
+
+    /// Sets offset of documents returned per group (group.offset)
+    pub fn offset(&self, offset: u32) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.offset = Some(offset);
+        grouping
+    }
+
+    /// Adds sort of documents inside each group (group.sort)
+    pub fn add_sort(&self, field: &str, order: SortOrder) -> Grouping {
+        let mut grouping = self.clone();
+        let sort_clause = SortClause{field: field.to_string(), order: order};
+        grouping.sorts = match grouping.sorts {
+            Some(mut s) => {
+                s.push(sort_clause);
+                Some(s)
+            },
+            None => Some(vec!(sort_clause))
+        };
+        grouping
+    }
+
+    /// Enables returning number of groups (group.ngroups)
+    pub fn ngroups(&self, ngroups: bool) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.ngroups = Some(ngroups);
+        grouping
+    }
+
+    /// Returns documents of the first group command in the main result list (group.main)
+    pub fn main(&self, main: bool) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.main = Some(main);
+        grouping
+    }
+
+    /// Sets grouped response format (group.format)
+    pub fn format(&self, format: GroupFormat) -> Grouping {
+        let mut grouping = self.clone();
+        grouping.format = Some(format);
+        grouping
+    }
+
+    /// Converts these options to URL pairs, including group=true
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("group".to_string(), "true".to_string()));
+        vec.extend(self.fields.iter().map(|x| ("group.field".to_string(), x.clone())));
+        vec.extend(self.queries.iter().map(|x| ("group.query".to_string(), x.clone())));
+        vec.extend(self.funcs.iter().map(|x| ("group.func".to_string(), x.clone())));
+        if let Some(limit) = self.limit {
+            vec.push(("group.limit".to_string(), limit.to_string()));
+        }
+        if let Some(offset) = self.offset {
+            vec.push(("group.offset".to_string(), offset.to_string()));
+        }
+        if let Some(ref sorts) = self.sorts {
+            let sort_url_params: Vec<String> = sorts.iter().map(|x| x.to_url_param()).collect();
+            vec.push(("group.sort".to_string(), sort_url_params.join(", ")));
+        }
+        if let Some(ngroups) = self.ngroups {
+            vec.push(("group.ngroups".to_string(), ngroups.to_string()));
+        }
+        if let Some(main) = self.main {
+            vec.push(("group.main".to_string(), main.to_string()));
+        }
+        if let Some(ref format) = self.format {
+            vec.push(("group.format".to_string(), format.to_url_param()));
+        }
+        vec
+    }
+}
+
+/// Represents handling of documents without a value in the collapse field (nullPolicy)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollapseNullPolicy {
+    /// Documents without a value are removed
+    Ignore,
+    /// Each document without a value is treated as a separate group
+    Expand,
+    /// All documents without a value are collapsed into a single group
+    Collapse
+}
+
+impl ToUrlParam for CollapseNullPolicy {
+    fn to_url_param(&self) -> String {
+        match *self {
+            CollapseNullPolicy::Ignore => "ignore".to_string(),
+            CollapseNullPolicy::Expand => "expand".to_string(),
+            CollapseNullPolicy::Collapse => "collapse".to_string()
+        }
+    }
+}
+
+/// Represents collapsing query parser filter ({!collapse}), which keeps a single
+/// document per unique value of a field
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collapse {
+    pub field: String,
+    /// Keeps document with the minimum value of a field or function
+    pub min: Option<String>,
+    /// Keeps document with the maximum value of a field or function
+    pub max: Option<String>,
+    /// Keeps the first document according to sort
+    pub sort: Option<String>,
+    pub null_policy: Option<CollapseNullPolicy>
+}
+
+impl Collapse {
+    /// Creates a new collapse on the given field, keeping the highest scoring document
+    pub fn new(field: &str) -> Collapse {
+        Collapse{field: field.to_string(), min: None, max: None, sort: None, null_policy: None}
+    }
+
+    /// Keeps document with the minimum value of a field or function
+    pub fn min(&self, min: &str) -> Collapse {
+        let mut collapse = self.clone();
+        collapse.min = Some(min.to_string());
+        collapse
+    }
+
+    /// Keeps document with the maximum value of a field or function
+    pub fn max(&self, max: &str) -> Collapse {
+        let mut collapse = self.clone();
+        collapse.max = Some(max.to_string());
+        collapse
+    }
+
+    /// Keeps the first document according to sort, for example `price asc`
+    pub fn sort(&self, sort: &str) -> Collapse {
+        let mut collapse = self.clone();
+        collapse.sort = Some(sort.to_string());
+        collapse
+    }
+
+    /// Sets handling of documents without a value
+    pub fn null_policy(&self, null_policy: CollapseNullPolicy) -> Collapse {
+        let mut collapse = self.clone();
+        collapse.null_policy = Some(null_policy);
+        collapse
+    }
+}
+
+impl ToUrlParam for Collapse {
+    fn to_url_param(&self) -> String {
+        let mut param = format!("{{!collapse field={}", self.field);
+        if let Some(ref min) = self.min {
+            param.push_str(&format!(" min={}", min));
+        }
+        if let Some(ref max) = self.max {
+            param.push_str(&format!(" max={}", max));
+        }
+        if let Some(ref sort) = self.sort {
+            param.push_str(&format!(" sort='{}'", sort));
+        }
+        if let Some(ref null_policy) = self.null_policy {
+            param.push_str(&format!(" nullPolicy={}", null_policy.to_url_param()));
+        }
+        param.push('}');
+        param
+    }
+}
+
+/// Represents expand component options (expand.*), which return documents
+/// collapsed by the collapse filter
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Expand {
+    pub rows: Option<u32>,
+    pub sort: Option<String>,
+    pub query: Option<String>,
+    pub filters: Vec<String>
+}
+
+impl Expand {
+    /// Creates expand options with Solr defaults
+    pub fn new() -> Expand {
+        Expand::default()
+    }
+
+    /// Sets number of documents returned per collapsed group (expand.rows)
+    pub fn rows(&self, rows: u32) -> Expand {
+        let mut expand = self.clone();
+        expand.rows = Some(rows);
+        expand
+    }
+
+    /// Sets sort of documents inside each group (expand.sort)
+    pub fn sort(&self, sort: &str) -> Expand {
+        let mut expand = self.clone();
+        expand.sort = Some(sort.to_string());
+        expand
+    }
+
+    /// Sets query used to expand groups instead of the main query (expand.q)
+    pub fn query(&self, query: &str) -> Expand {
+        let mut expand = self.clone();
+        expand.query = Some(query.to_string());
+        expand
+    }
+
+    /// Adds filter used to expand groups instead of the main filters (expand.fq)
+    pub fn add_filter(&self, filter: &str) -> Expand {
+        let mut expand = self.clone();
+        expand.filters.push(filter.to_string());
+        expand
+    }
+
+    /// Converts these options to URL pairs, including expand=true
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("expand".to_string(), "true".to_string()));
+        if let Some(rows) = self.rows {
+            vec.push(("expand.rows".to_string(), rows.to_string()));
+        }
+        if let Some(ref sort) = self.sort {
+            vec.push(("expand.sort".to_string(), sort.clone()));
+        }
+        if let Some(ref query) = self.query {
+            vec.push(("expand.q".to_string(), query.clone()));
+        }
+        vec.extend(self.filters.iter().map(|x| ("expand.fq".to_string(), x.clone())));
+        vec
+    }
+}
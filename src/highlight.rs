@@ -0,0 +1,127 @@
+use query::ToUrlParam;
+
+/// Represents highlighter implementation (hl.method)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HighlightMethod {
+    Unified,
+    Original,
+    FastVector
+}
+
+impl ToUrlParam for HighlightMethod {
+    fn to_url_param(&self) -> String {
+        match *self {
+            HighlightMethod::Unified => "unified".to_string(),
+            HighlightMethod::Original => "original".to_string(),
+            HighlightMethod::FastVector => "fastVector".to_string()
+        }
+    }
+}
+
+/// Represents highlighting options (hl.*).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Highlighting {
+    pub fields: Vec<String>,
+    pub method: Option<HighlightMethod>,
+    pub snippets: Option<u32>,
+    pub fragsize: Option<u32>,
+    pub simple_pre: Option<String>,
+    pub simple_post: Option<String>,
+    pub query: Option<String>,
+    pub require_field_match: Option<bool>
+}
+
+impl Highlighting {
+    /// Creates highlighting options with Solr defaults
+    pub fn new() -> Highlighting {
+        Highlighting::default()
+    }
+
+    /// Adds field to highlight (hl.fl)
+    pub fn add_field(&self, field: &str) -> Highlighting {
+        let mut hl = self.clone();
+        hl.fields.push(field.to_string());
+        hl
+    }
+
+    /// Sets highlighter implementation (hl.method)
+    pub fn method(&self, method: HighlightMethod) -> Highlighting {
+        let mut hl = self.clone();
+        hl.method = Some(method);
+        hl
+    }
+
+    /// Sets maximum number of snippets per field (hl.snippets)
+    pub fn snippets(&self, snippets: u32) -> Highlighting {
+        let mut hl = self.clone();
+        hl.snippets = Some(snippets);
+        hl
+    }
+
+    /// Sets approximate snippet size in characters (hl.fragsize)
+    pub fn fragsize(&self, fragsize: u32) -> Highlighting {
+        let mut hl = self.clone();
+        hl.fragsize = Some(fragsize);
+        hl
+    }
+
+    /// Sets text surrounding highlighted terms (hl.simple.pre and hl.simple.post).
+    /// For the unified highlighter hl.tag.pre and hl.tag.post are sent as well.
+    pub fn tags(&self, pre: &str, post: &str) -> Highlighting {
+        let mut hl = self.clone();
+        hl.simple_pre = Some(pre.to_string());
+        hl.simple_post = Some(post.to_string());
+        hl
+    }
+
+    /// Sets query to highlight instead of the main query (hl.q)
+    pub fn query(&self, query: &str) -> Highlighting {
+        let mut hl = self.clone();
+        hl.query = Some(query.to_string());
+        hl
+    }
+
+    /// Sets whether only terms matching the highlighted field are highlighted (hl.requireFieldMatch)
+    pub fn require_field_match(&self, require_field_match: bool) -> Highlighting {
+        let mut hl = self.clone();
+        hl.require_field_match = Some(require_field_match);
+        hl
+    }
+
+    /// Converts these options to URL pairs, including hl=true
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("hl".to_string(), "true".to_string()));
+        if !self.fields.is_empty() {
+            vec.push(("hl.fl".to_string(), self.fields.join(",")));
+        }
+        if let Some(ref method) = self.method {
+            vec.push(("hl.method".to_string(), method.to_url_param()));
+        }
+        if let Some(snippets) = self.snippets {
+            vec.push(("hl.snippets".to_string(), snippets.to_string()));
+        }
+        if let Some(fragsize) = self.fragsize {
+            vec.push(("hl.fragsize".to_string(), fragsize.to_string()));
+        }
+        let unified = self.method == Some(HighlightMethod::Unified);
+        if let Some(ref pre) = self.simple_pre {
+            vec.push(("hl.simple.pre".to_string(), pre.clone()));
+            if unified {
+                vec.push(("hl.tag.pre".to_string(), pre.clone()));
+            }
+        }
+        if let Some(ref post) = self.simple_post {
+            vec.push(("hl.simple.post".to_string(), post.clone()));
+            if unified {
+                vec.push(("hl.tag.post".to_string(), post.clone()));
+            }
+        }
+        if let Some(ref query) = self.query {
+            vec.push(("hl.q".to_string(), query.clone()));
+        }
+        if let Some(require_field_match) = self.require_field_match {
+            vec.push(("hl.requireFieldMatch".to_string(), require_field_match.to_string()));
+        }
+        vec
+    }
+}
@@ -1,5 +1,6 @@
 use url::Url;
 use hyper::Client;
+use hyper::client::Response;
 use hyper::header::{ContentType};
 use hyper::status::StatusCode;
 use hyper::error::Error;
@@ -35,11 +36,33 @@ pub fn get(url: &Url) -> Result<HttpResponse, Error> {
 
 
 pub fn post_json(url: &Url, body: &str) -> Result<HttpResponse, Error> {
+    post(url, ContentType::json(), body)
+}
+
+pub fn post_text(url: &Url, body: &str) -> Result<HttpResponse, Error> {
+    post(url, ContentType::plaintext(), body)
+}
+
+fn post(url: &Url, content_type: ContentType, body: &str) -> Result<HttpResponse, Error> {
     let mut client = Client::new();
     let result_response = client.post(&url.to_string())
-        .header(ContentType::json())
+        .header(content_type)
+        .body(body)
+        .send();
+    read_response(result_response)
+}
+
+/// Posts body read from a stream, sent with chunked transfer encoding without buffering it
+pub fn post_stream<R: Read>(url: &Url, content_type: ContentType, body: &mut R) -> Result<HttpResponse, Error> {
+    let client = Client::new();
+    let result_response = client.post(&url.to_string())
+        .header(content_type)
         .body(body)
         .send();
+    read_response(result_response)
+}
+
+fn read_response(result_response: Result<Response, Error>) -> Result<HttpResponse, Error> {
     match result_response {
         Ok(mut res) => {
             let mut body = String::new();
@@ -55,4 +78,4 @@ pub fn post_json(url: &Url, body: &str) -> Result<HttpResponse, Error> {
         },
         Err(err) => Err(err)
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,424 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+use facet::{FacetRangeOther, FacetRangeInclude};
+use query::ToUrlParam;
+
+/// Represents aggregation function of the JSON Facet API
+#[derive(Clone, Debug, PartialEq)]
+pub enum Aggregation {
+    Sum(String),
+    Avg(String),
+    Min(String),
+    Max(String),
+    /// Exact number of unique values
+    Unique(String),
+    /// Approximate number of unique values (HyperLogLog)
+    Hll(String),
+    SumSq(String),
+    Variance(String),
+    Stddev(String),
+    /// Percentiles of a field, for example 50 and 99
+    Percentile(String, Vec<f64>),
+    /// Arbitrary aggregation, for example `sum(mul(price,popularity))`
+    Raw(String)
+}
+
+impl ToUrlParam for Aggregation {
+    fn to_url_param(&self) -> String {
+        match *self {
+            Aggregation::Sum(ref f) => format!("sum({})", f),
+            Aggregation::Avg(ref f) => format!("avg({})", f),
+            Aggregation::Min(ref f) => format!("min({})", f),
+            Aggregation::Max(ref f) => format!("max({})", f),
+            Aggregation::Unique(ref f) => format!("unique({})", f),
+            Aggregation::Hll(ref f) => format!("hll({})", f),
+            Aggregation::SumSq(ref f) => format!("sumsq({})", f),
+            Aggregation::Variance(ref f) => format!("variance({})", f),
+            Aggregation::Stddev(ref f) => format!("stddev({})", f),
+            Aggregation::Percentile(ref f, ref p) => {
+                let percentiles: Vec<String> = p.iter().map(|x| x.to_string()).collect();
+                format!("percentile({},{})", f, percentiles.join(","))
+            },
+            Aggregation::Raw(ref a) => a.clone()
+        }
+    }
+}
+
+/// Represents a single facet of the JSON Facet API (json.facet)
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonFacet {
+    Terms(TermsFacet),
+    Range(RangeFacet),
+    Query(QueryFacet),
+    Heatmap(HeatmapFacet),
+    Aggregation(Aggregation)
+}
+
+impl JsonFacet {
+    /// Converts this facet to its JSON representation
+    pub fn to_json(&self) -> Json {
+        match *self {
+            JsonFacet::Terms(ref f) => f.to_json(),
+            JsonFacet::Range(ref f) => f.to_json(),
+            JsonFacet::Query(ref f) => f.to_json(),
+            JsonFacet::Heatmap(ref f) => f.to_json(),
+            JsonFacet::Aggregation(ref a) => Json::String(a.to_url_param())
+        }
+    }
+}
+
+impl From<TermsFacet> for JsonFacet {
+    fn from(facet: TermsFacet) -> JsonFacet { JsonFacet::Terms(facet) }
+}
+
+impl From<RangeFacet> for JsonFacet {
+    fn from(facet: RangeFacet) -> JsonFacet { JsonFacet::Range(facet) }
+}
+
+impl From<QueryFacet> for JsonFacet {
+    fn from(facet: QueryFacet) -> JsonFacet { JsonFacet::Query(facet) }
+}
+
+impl From<HeatmapFacet> for JsonFacet {
+    fn from(facet: HeatmapFacet) -> JsonFacet { JsonFacet::Heatmap(facet) }
+}
+
+impl From<Aggregation> for JsonFacet {
+    fn from(aggregation: Aggregation) -> JsonFacet { JsonFacet::Aggregation(aggregation) }
+}
+
+/// Converts named facets to a JSON object, as used by json.facet and nested facet blocks
+pub fn facets_to_json(facets: &[(String, JsonFacet)]) -> Json {
+    let mut tm = BTreeMap::new();
+    for &(ref name, ref facet) in facets.iter() {
+        tm.insert(name.clone(), facet.to_json());
+    }
+    Json::Object(tm)
+}
+
+fn push_facet(facets: &[(String, JsonFacet)], name: &str, facet: JsonFacet) -> Vec<(String, JsonFacet)> {
+    let mut new_facets = facets.to_vec();
+    new_facets.push((name.to_string(), facet));
+    new_facets
+}
+
+/// Terms facet, buckets documents by indexed terms of a field
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermsFacet {
+    pub field: String,
+    pub offset: Option<u64>,
+    pub limit: Option<i64>,
+    pub sort: Option<String>,
+    pub mincount: Option<u64>,
+    pub missing: Option<bool>,
+    pub num_buckets: Option<bool>,
+    pub all_buckets: Option<bool>,
+    pub prefix: Option<String>,
+    pub facets: Vec<(String, JsonFacet)>
+}
+
+impl TermsFacet {
+    /// Creates a new terms facet on the given field
+    pub fn new(field: &str) -> TermsFacet {
+        TermsFacet{field: field.to_string(), offset: None, limit: None, sort: None, mincount: None,
+            missing: None, num_buckets: None, all_buckets: None, prefix: None, facets: Vec::new()}
+    }
+
+    /// Sets number of buckets to skip
+    pub fn offset(&self, offset: u64) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.offset = Some(offset);
+        facet
+    }
+
+    /// Sets maximum number of buckets, -1 means no limit
+    pub fn limit(&self, limit: i64) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.limit = Some(limit);
+        facet
+    }
+
+    /// Sets buckets ordering, for example `count desc` or `avg_price asc`
+    pub fn sort(&self, sort: &str) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.sort = Some(sort.to_string());
+        facet
+    }
+
+    /// Sets minimum count for a bucket to be returned
+    pub fn mincount(&self, mincount: u64) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.mincount = Some(mincount);
+        facet
+    }
+
+    /// Enables special bucket for documents without a value
+    pub fn missing(&self, missing: bool) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.missing = Some(missing);
+        facet
+    }
+
+    /// Enables returning total number of buckets (numBuckets)
+    pub fn num_buckets(&self, num_buckets: bool) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.num_buckets = Some(num_buckets);
+        facet
+    }
+
+    /// Enables special bucket aggregating all buckets (allBuckets)
+    pub fn all_buckets(&self, all_buckets: bool) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.all_buckets = Some(all_buckets);
+        facet
+    }
+
+    /// Limits buckets to terms starting with prefix
+    pub fn prefix(&self, prefix: &str) -> TermsFacet {
+        let mut facet = self.clone();
+        facet.prefix = Some(prefix.to_string());
+        facet
+    }
+
+    /// Adds named sub-facet or aggregation, computed for every bucket
+    pub fn add_facet<F: Into<JsonFacet>>(&self, name: &str, facet: F) -> TermsFacet {
+        let mut terms_facet = self.clone();
+        terms_facet.facets = push_facet(&self.facets, name, facet.into());
+        terms_facet
+    }
+
+    /// Converts this facet to its JSON representation
+    pub fn to_json(&self) -> Json {
+        let mut tm = BTreeMap::new();
+        tm.insert("type".to_string(), Json::String("terms".to_string()));
+        tm.insert("field".to_string(), Json::String(self.field.clone()));
+        if let Some(offset) = self.offset {
+            tm.insert("offset".to_string(), Json::U64(offset));
+        }
+        if let Some(limit) = self.limit {
+            tm.insert("limit".to_string(), Json::I64(limit));
+        }
+        if let Some(ref sort) = self.sort {
+            tm.insert("sort".to_string(), Json::String(sort.clone()));
+        }
+        if let Some(mincount) = self.mincount {
+            tm.insert("mincount".to_string(), Json::U64(mincount));
+        }
+        if let Some(missing) = self.missing {
+            tm.insert("missing".to_string(), Json::Boolean(missing));
+        }
+        if let Some(num_buckets) = self.num_buckets {
+            tm.insert("numBuckets".to_string(), Json::Boolean(num_buckets));
+        }
+        if let Some(all_buckets) = self.all_buckets {
+            tm.insert("allBuckets".to_string(), Json::Boolean(all_buckets));
+        }
+        if let Some(ref prefix) = self.prefix {
+            tm.insert("prefix".to_string(), Json::String(prefix.clone()));
+        }
+        if !self.facets.is_empty() {
+            tm.insert("facet".to_string(), facets_to_json(&self.facets));
+        }
+        Json::Object(tm)
+    }
+}
+
+/// Range facet, buckets documents by ranges of a numeric or date field
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeFacet {
+    pub field: String,
+    pub start: String,
+    pub end: String,
+    pub gap: String,
+    pub hardend: Option<bool>,
+    pub other: Option<Vec<FacetRangeOther>>,
+    pub include: Option<Vec<FacetRangeInclude>>,
+    pub facets: Vec<(String, JsonFacet)>
+}
+
+impl RangeFacet {
+    /// Creates a new range facet, bounds and gap are Solr values like `0` or `NOW/DAY` and `+1DAY`
+    pub fn new(field: &str, start: &str, end: &str, gap: &str) -> RangeFacet {
+        RangeFacet{field: field.to_string(), start: start.to_string(), end: end.to_string(), gap: gap.to_string(),
+            hardend: None, other: None, include: None, facets: Vec::new()}
+    }
+
+    /// Creates a new range facet on a numeric field
+    pub fn numeric(field: &str, start: f64, end: f64, gap: f64) -> RangeFacet {
+        RangeFacet::new(field, &start.to_string(), &end.to_string(), &gap.to_string())
+    }
+
+    /// Sets whether the last range is truncated at end
+    pub fn hardend(&self, hardend: bool) -> RangeFacet {
+        let mut facet = self.clone();
+        facet.hardend = Some(hardend);
+        facet
+    }
+
+    /// Adds additional counts to compute
+    pub fn add_other(&self, other: FacetRangeOther) -> RangeFacet {
+        let mut facet = self.clone();
+        facet.other = match facet.other {
+            Some(mut o) => {
+                o.push(other);
+                Some(o)
+            },
+            None => Some(vec!(other))
+        };
+        facet
+    }
+
+    /// Adds bounds inclusion rule
+    pub fn add_include(&self, include: FacetRangeInclude) -> RangeFacet {
+        let mut facet = self.clone();
+        facet.include = match facet.include {
+            Some(mut i) => {
+                i.push(include);
+                Some(i)
+            },
+            None => Some(vec!(include))
+        };
+        facet
+    }
+
+    /// Adds named sub-facet or aggregation, computed for every bucket
+    pub fn add_facet<F: Into<JsonFacet>>(&self, name: &str, facet: F) -> RangeFacet {
+        let mut range_facet = self.clone();
+        range_facet.facets = push_facet(&self.facets, name, facet.into());
+        range_facet
+    }
+
+    /// Converts this facet to its JSON representation
+    pub fn to_json(&self) -> Json {
+        let mut tm = BTreeMap::new();
+        tm.insert("type".to_string(), Json::String("range".to_string()));
+        tm.insert("field".to_string(), Json::String(self.field.clone()));
+        tm.insert("start".to_string(), Json::String(self.start.clone()));
+        tm.insert("end".to_string(), Json::String(self.end.clone()));
+        tm.insert("gap".to_string(), Json::String(self.gap.clone()));
+        if let Some(hardend) = self.hardend {
+            tm.insert("hardend".to_string(), Json::Boolean(hardend));
+        }
+        if let Some(ref other) = self.other {
+            tm.insert("other".to_string(), Json::Array(other.iter().map(|x| Json::String(x.to_url_param())).collect()));
+        }
+        if let Some(ref include) = self.include {
+            tm.insert("include".to_string(), Json::Array(include.iter().map(|x| Json::String(x.to_url_param())).collect()));
+        }
+        if !self.facets.is_empty() {
+            tm.insert("facet".to_string(), facets_to_json(&self.facets));
+        }
+        Json::Object(tm)
+    }
+}
+
+/// Query facet, creates a single bucket of documents matching the query
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryFacet {
+    pub query: String,
+    pub facets: Vec<(String, JsonFacet)>
+}
+
+impl QueryFacet {
+    /// Creates a new query facet
+    pub fn new(query: &str) -> QueryFacet {
+        QueryFacet{query: query.to_string(), facets: Vec::new()}
+    }
+
+    /// Adds named sub-facet or aggregation, computed for the bucket
+    pub fn add_facet<F: Into<JsonFacet>>(&self, name: &str, facet: F) -> QueryFacet {
+        let mut query_facet = self.clone();
+        query_facet.facets = push_facet(&self.facets, name, facet.into());
+        query_facet
+    }
+
+    /// Converts this facet to its JSON representation
+    pub fn to_json(&self) -> Json {
+        let mut tm = BTreeMap::new();
+        tm.insert("type".to_string(), Json::String("query".to_string()));
+        tm.insert("q".to_string(), Json::String(self.query.clone()));
+        if !self.facets.is_empty() {
+            tm.insert("facet".to_string(), facets_to_json(&self.facets));
+        }
+        Json::Object(tm)
+    }
+}
+
+/// Heatmap facet, counts documents in a grid over a spatial (RPT) field
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeatmapFacet {
+    pub field: String,
+    /// Region to compute the heatmap for, for example `["-180 -90" TO "180 90"]`
+    pub geom: Option<String>,
+    pub grid_level: Option<u32>,
+    pub dist_err_pct: Option<f64>,
+    pub max_cells: Option<u64>,
+    /// Either `ints2D` (default) or `png`
+    pub format: Option<String>
+}
+
+impl HeatmapFacet {
+    /// Creates a new heatmap facet on the given field
+    pub fn new(field: &str) -> HeatmapFacet {
+        HeatmapFacet{field: field.to_string(), geom: None, grid_level: None, dist_err_pct: None,
+            max_cells: None, format: None}
+    }
+
+    /// Sets region to compute the heatmap for
+    pub fn geom(&self, geom: &str) -> HeatmapFacet {
+        let mut facet = self.clone();
+        facet.geom = Some(geom.to_string());
+        facet
+    }
+
+    /// Sets grid level, which determines the cell size
+    pub fn grid_level(&self, grid_level: u32) -> HeatmapFacet {
+        let mut facet = self.clone();
+        facet.grid_level = Some(grid_level);
+        facet
+    }
+
+    /// Sets fraction of the region size used to pick the grid level
+    pub fn dist_err_pct(&self, dist_err_pct: f64) -> HeatmapFacet {
+        let mut facet = self.clone();
+        facet.dist_err_pct = Some(dist_err_pct);
+        facet
+    }
+
+    /// Sets maximum number of cells
+    pub fn max_cells(&self, max_cells: u64) -> HeatmapFacet {
+        let mut facet = self.clone();
+        facet.max_cells = Some(max_cells);
+        facet
+    }
+
+    /// Sets output format, `ints2D` or `png`
+    pub fn format(&self, format: &str) -> HeatmapFacet {
+        let mut facet = self.clone();
+        facet.format = Some(format.to_string());
+        facet
+    }
+
+    /// Converts this facet to its JSON representation
+    pub fn to_json(&self) -> Json {
+        let mut tm = BTreeMap::new();
+        tm.insert("type".to_string(), Json::String("heatmap".to_string()));
+        tm.insert("field".to_string(), Json::String(self.field.clone()));
+        if let Some(ref geom) = self.geom {
+            tm.insert("geom".to_string(), Json::String(geom.clone()));
+        }
+        if let Some(grid_level) = self.grid_level {
+            tm.insert("gridLevel".to_string(), Json::U64(grid_level as u64));
+        }
+        if let Some(dist_err_pct) = self.dist_err_pct {
+            tm.insert("distErrPct".to_string(), Json::F64(dist_err_pct));
+        }
+        if let Some(max_cells) = self.max_cells {
+            tm.insert("maxCells".to_string(), Json::U64(max_cells));
+        }
+        if let Some(ref format) = self.format {
+            tm.insert("format".to_string(), Json::String(format.clone()));
+        }
+        Json::Object(tm)
+    }
+}
@@ -65,6 +65,105 @@ fn main(){
 }
 ```
 
+### Typed fields
+
+```ignore
+extern crate time;
+
+let mut document = SolrDocument::new();
+document.add_field("id", "5");
+document.add_i64("pages", 320);
+document.add_f64("price", 9.99);
+document.add_bool("in_stock", true);
+document.add_date("published", &time::now_utc());
+document.add_binary("thumbnail", &[0x89, 0x50, 0x4e, 0x47]);
+
+// typed lookups on query results fail on missing fields and type mismatch
+for doc in solr_response.items.iter() {
+    let pages = doc.get_i64("pages");
+    let published = doc.get_date("published");
+}
+```
+
+### Adding documents with nested child documents
+
+```ignore
+let mut product = SolrDocument::new();
+product.add_field("id", "p1");
+product.add_field("type", "product");
+let mut variant = SolrDocument::new();
+variant.add_field("id", "v1");
+variant.add_field("color", "red");
+// anonymous children, or product.add_children("variants", vec!(variant)) with nested schema (Solr 8+)
+product.add_child(variant);
+solr.add_and_commit(&product);
+```
+
+### Typed documents
+
+Structs are mapped to documents with `ToSolrDocument` and `FromSolrDocument`,
+which can be derived with the `heliotrope_derive` crate.
+
+```ignore
+#[macro_use]
+extern crate heliotrope_derive;
+use std::collections::BTreeMap;
+
+#[derive(ToSolrDocument, FromSolrDocument)]
+struct Book {
+    id: String,
+    #[solr(rename = "title_t")]
+    title: String,
+    // omitted when None, None when missing
+    pages: Option<i64>,
+    // multi-valued field
+    authors: Vec<String>,
+    // dynamic fields attr_*, keyed by the part matching *
+    #[solr(dynamic = "attr_*")]
+    attributes: BTreeMap<String, String>,
+    #[solr(skip)]
+    cached: bool
+}
+
+solr.add_items(&books);
+let books: Vec<Book> = solr.query_as(&SolrQuery::new("*:*")).unwrap();
+```
+
+### Atomic updates
+
+Updates single fields of existing documents, keeping the other fields as they are.
+
+```ignore
+use heliotrope::AtomicUpdate;
+
+let update = AtomicUpdate::new("1")
+    .set("title", "How to train your dragon 2")
+    .inc("views", 1)
+    .add_distinct("tags", vec!("dragons", "vikings"))
+    .remove_regex("tags", "draft.*")
+    .unset("discount");
+solr.update_fields(&update);
+solr.commit();
+```
+
+### Optimistic concurrency
+
+```ignore
+use heliotrope::{AtomicUpdate, DocumentVersion, SolrErrorKind};
+
+// documents returned by a query keep their _version_, so re-indexing fails when changed meanwhile
+let mut document = solr_response.items.remove(0);
+document.set_version(DocumentVersion::Exactly(document.get_version().unwrap()));
+match solr.add(&document) {
+    Ok(_) => println!("saved"),
+    Err(SolrError{kind: SolrErrorKind::VersionConflict(conflict), ..}) => println!("{} was modified, retrying", conflict.id),
+    Err(solr_error) => println!("{}", solr_error.message)
+}
+
+// creates the document only when it doesn't exist yet
+solr.update_fields(&AtomicUpdate::new("2").version(DocumentVersion::MustNotExist).set("title", "Moby Dick"));
+```
+
 ### Adding multiple document at once
 
 ```ignore
@@ -91,6 +190,51 @@ match solr.add_many_and_commit(vec!(&document1, &document2)) {
 }
 ```
 
+### Bulk indexing
+
+Large streams of documents are sent in batches from a pool of worker threads.
+Failed batches are retried, documents rejected by Solr are reported one by one.
+
+```ignore
+use std::time::Duration;
+
+let indexer = solr.bulk_indexer()
+    .batch_size(1000)
+    .batch_bytes(10 * 1024 * 1024)
+    .workers(8)
+    .max_retries(5)
+    .retry_delay(Duration::from_secs(1))
+    .commit_within(60000);
+let stats = indexer.index(documents);
+println!("{} indexed, {} failed, {:.0} docs/s", stats.indexed, stats.failed, stats.docs_per_second());
+for failure in stats.failures.iter() {
+    println!("{:?}: {}", failure.id, failure.error.message);
+}
+
+// or adding documents one by one, blocking while workers are busy
+let mut session = indexer.start();
+for document in documents {
+    session.add(&document);
+}
+let stats = session.finish();
+```
+
+### Streaming updates
+
+Update bodies are streamed from any `Read` source, for example a dump on disk.
+
+```ignore
+use std::fs::File;
+use heliotrope::{UpdateFormat, CsvOptions};
+
+let mut dump = File::open("books.jsonl").unwrap();
+solr.update_stream(&UpdateFormat::JsonLines, &mut dump);
+
+let mut csv = File::open("books.csv").unwrap();
+let format = UpdateFormat::Csv(CsvOptions::new().separator(';').split_field("authors", '|'));
+solr.update_stream_and_commit(&format, &mut csv);
+```
+
 ## Querying
 
 ```ignore
@@ -142,13 +286,222 @@ let query = SolrQuery::new("*:*")
 let query = SolrQuery::new("manufacturer:Sony").start(100).rows(50);
 ```
 
+### Typed queries
+
+Values of typed queries are escaped, so user input can't break the query or inject clauses.
+
+```ignore
+use heliotrope::{Query, BooleanQuery};
+
+let user_input = "Dragon: (the sequel)";
+let query = SolrQuery::new(&BooleanQuery::new()
+        .must(Query::phrase("title", user_input).boost(2.0))
+        .must_not(Query::term("type", "Comic")))
+    .add_filter(&Query::range("year", Some("2000"), None, true, true));
+```
+
+### Nested documents
+
+```ignore
+use heliotrope::{Query, ChildTransformer};
+
+// products having a red variant, returned with their red variants
+let query = SolrQuery::new(&Query::parent("type:product", Query::term("color", "red")))
+    .add_field("*")
+    .add_child_transformer(ChildTransformer::new().parent_filter("type:product").child_filter("color:red").limit(5));
+if let Ok(solr_response) = solr.query(&query) {
+    for product in solr_response.items.iter() {
+        println!("{:?} has {} matching variants", product.get("id"), product.children.len());
+    }
+}
+```
+
+### Spatial search
+
+```ignore
+use heliotrope::{GeoPoint, SpatialPredicate};
+
+// stores within 5 km, nearest first, with their distance
+let here = GeoPoint::new(45.15, -93.85);
+let query = SolrQuery::new("*:*")
+    .add_filter(&here.geofilt("store", 5.0))
+    .add_sort(&here.geodist("store"), SortOrder::Ascending)
+    .add_field("*")
+    .add_field(&here.distance_field("dist", "store"));
+
+// shapes intersecting a polygon
+let area = GeoPoint::polygon_wkt(&[GeoPoint::new(45.0, -94.0), GeoPoint::new(46.0, -94.0), GeoPoint::new(46.0, -93.0)]);
+let query = SolrQuery::new("*:*").add_filter(&SpatialPredicate::Intersects.filter("geo", &area));
+```
+
+### Faceting
+
+```ignore
+use heliotrope::{FacetField, FacetSort};
+
+let query = SolrQuery::new("*:*")
+    .facet_mincount(1)
+    .add_facet_field(FacetField::new("city").limit(20).sort(FacetSort::Count));
+if let Ok(solr_response) = solr.query(&query) {
+    if let Some(facet_counts) = solr_response.facet_counts {
+        for facet_count in facet_counts.field("city").unwrap().counts.iter() {
+            println!("{}: {}", facet_count.term, facet_count.count);
+        }
+    }
+}
+```
+
+### Highlighting
+
+```ignore
+use heliotrope::{Highlighting, HighlightMethod};
+
+let query = SolrQuery::new("body:dragon")
+    .highlight(Highlighting::new().add_field("body").method(HighlightMethod::Unified).snippets(2));
+if let Ok(solr_response) = solr.query(&query) {
+    for (doc, highlights) in solr_response.highlighted_items("id") {
+        println!("{:?}: {:?}", doc.get("id"), highlights);
+    }
+}
+```
+
+### Statistics
+
+```ignore
+use heliotrope::{StatsField, Stat};
+
+let query = SolrQuery::new("*:*").rows(0)
+    .add_stats_field(StatsField::new("price").add_stat(Stat::Min).add_stat(Stat::Max).percentiles(&[50.0, 99.0]));
+if let Ok(solr_response) = solr.query(&query) {
+    let stats = solr_response.stats.unwrap();
+    let price = stats.field("price").unwrap();
+    println!("{:?} - {:?}, median {:?}", price.min, price.max, price.percentiles.get(0));
+}
+```
+
+### Spellcheck
+
+```ignore
+use heliotrope::Spellcheck;
+
+let query = SolrQuery::new("dragn ridr")
+    .spellcheck(Spellcheck::new().collate(true).max_collation_tries(5).collate_extended_results(true));
+if let Ok(solr_response) = solr.query(&query) {
+    if let Some(collation) = solr_response.spellcheck.as_ref().and_then(|x| x.best_collation()) {
+        println!("Did you mean: {}", collation);
+    }
+}
+```
+
+### Autocomplete
+
+```ignore
+use heliotrope::SuggestQuery;
+
+let query = SuggestQuery::new("drag").add_dictionary("titleSuggester").count(5).cfq("Book");
+if let Ok(suggest_response) = solr.suggest(&query) {
+    for suggestion in suggest_response.dictionary("titleSuggester").unwrap().suggestions.iter() {
+        println!("{} ({})", suggestion.term, suggestion.weight);
+    }
+}
+```
+
+### Indexed terms
+
+```ignore
+use heliotrope::{TermsQuery, TermsSort};
+
+let query = TermsQuery::new("cat").prefix("ele").mincount(1).limit(20).sort(TermsSort::Index);
+if let Ok(terms_response) = solr.terms(&query) {
+    for term in terms_response.field("cat").unwrap().iter() {
+        println!("{}: {}", term.term, term.frequency);
+    }
+}
+```
+
+### More like this
+
+```ignore
+use heliotrope::{MoreLikeThis, MoreLikeThisQuery};
+
+// similar documents for each result
+let query = SolrQuery::new("body:dragon")
+    .more_like_this(MoreLikeThis::new().add_field("body").min_tf(1).count(3));
+if let Ok(solr_response) = solr.query(&query) {
+    if let Some(similar) = solr_response.more_like_this {
+        for (id, doclist) in similar.iter() {
+            println!("{}: {} similar documents", id, doclist.total);
+        }
+    }
+}
+
+// documents similar to a raw text, using the /mlt handler
+let query = MoreLikeThisQuery::from_text("Vala Morgulis")
+    .options(MoreLikeThis::new().add_field("body").min_tf(1).min_df(1))
+    .rows(5);
+let solr_response = solr.more_like_this(&query);
+```
+
+### JSON Facet API
+
+```ignore
+use heliotrope::{TermsFacet, Aggregation, JsonFacetResult};
+
+let query = SolrQuery::new("*:*")
+    .add_json_facet("categories", TermsFacet::new("cat")
+        .sort("avg_price desc")
+        .add_facet("avg_price", Aggregation::Avg("price".to_string())));
+if let Ok(solr_response) = solr.query(&query) {
+    let root = solr_response.json_facets.unwrap();
+    if let Some(&JsonFacetResult::Buckets(ref categories)) = root.facet("categories") {
+        for bucket in categories.buckets.iter() {
+            println!("{:?}: {:?}", bucket.val, bucket.metric("avg_price"));
+        }
+    }
+}
+```
+
+### Deep paging
+
+```ignore
+// iterating over all documents, 1000 per request
+let query = SolrQuery::new("*:*").add_sort("id", SortOrder::Ascending).rows(1000);
+for doc in solr.query_cursor(&query, "id").unwrap() {
+    match doc {
+        Ok(doc) => println!("{:?}", doc),
+        Err(solr_error) => println!("{}", solr_error.message)
+    }
+}
+```
+
+### Update requests
+
+Adds, atomic updates and deletes can be sent in a single request, with commit options.
+
+```ignore
+use heliotrope::{UpdateRequest, AtomicUpdate};
+
+let request = UpdateRequest::new()
+    .add(&document)
+    .update_fields(&AtomicUpdate::new("2").inc("views", 1))
+    .delete_by_ids(&["3", "4"])
+    .delete_by_query("type:Draft")
+    .commit_within(5000)
+    .update_chain("dedupe");
+solr.update(&request);
+
+// soft commit, making changes visible without a hard commit
+solr.update(&UpdateRequest::new().soft_commit().wait_searcher(false));
+```
+
 ### Delete documents by ID
 
 ```ignore
 solr.delete_by_id("99");
 ```
 
-Note that `delete_by_id` commits automatically after every delete request
+Note that `delete_by_id` commits automatically after every delete request,
+use `UpdateRequest` to delete without committing
 */
 
 #![crate_name="heliotrope"]
@@ -156,15 +509,66 @@ Note that `delete_by_id` commits automatically after every delete request
 extern crate rustc_serialize;
 extern crate url;
 extern crate hyper;
+extern crate time;
 
 pub use self::client::SolrClient;
-pub use self::document::SolrDocument;
+pub use self::cursor::SolrCursor;
+pub use self::document::{SolrDocument, SolrField, SolrValue, DocumentVersion};
 pub use self::query::{SolrQuery, SortClause, SortOrder};
+pub use self::mapping::{ToSolrDocument, FromSolrDocument, ToSolrValue, FromSolrValue, DynamicFields};
+pub use self::query_dsl::{Query, BooleanQuery, LocalParams, ToQueryString, escape};
 pub use self::request::SolrDeleteRequest;
+pub use self::atomic_update::{AtomicUpdate, FieldUpdate, UpdateModifier};
+pub use self::update_request::{UpdateRequest, UpdateCommand};
+pub use self::bulk::{BulkIndexer, BulkSession, BulkIndexStats, DocumentFailure};
+pub use self::update_stream::{UpdateFormat, CsvOptions};
+pub use self::facet::{FacetField, FacetOptions, FacetSort};
+pub use self::facet::{FacetRange, FacetRangeOther, FacetRangeInclude, FacetInterval, FacetPivot};
+pub use self::highlight::{Highlighting, HighlightMethod};
+pub use self::spellcheck::Spellcheck;
+pub use self::suggest::SuggestQuery;
+pub use self::terms::{TermsQuery, TermsSort};
+pub use self::stats::{StatsField, Stat};
+pub use self::block_join::ChildTransformer;
+pub use self::geo::{GeoPoint, SpatialPredicate};
+pub use self::more_like_this::{MoreLikeThis, MoreLikeThisQuery, MoreLikeThisSeed};
+pub use self::dismax::{QueryParser, DisMax, FieldBoost, MinimumMatch};
+pub use self::group::{Grouping, GroupFormat, Collapse, CollapseNullPolicy, Expand};
+pub use self::json_facet::{JsonFacet, TermsFacet, RangeFacet, QueryFacet, HeatmapFacet, Aggregation};
+pub use self::response::{SolrError, SolrErrorKind, VersionConflict, SolrUpdateResponse};
+pub use self::response::{SolrQueryResponse, SolrFacetCounts, FacetFieldCounts, FacetCount};
+pub use self::response::{SolrSuggestResponse, SuggestResult, Suggestion};
+pub use self::response::{SolrTermsResponse, TermFrequency};
+pub use self::response::{FacetRangeCounts, FacetRangeBucket, FacetIntervalCounts, FacetIntervalCount};
+pub use self::response::{RangeValue, RangeGap, IntervalBounds, FacetPivotCounts, PivotEntry};
+pub use self::response::{SolrHighlighting, FieldHighlights};
+pub use self::response::{SolrStats, FieldStats};
+pub use self::response::{SolrSpellcheck, SpellcheckSuggestion, SpellcheckAlternative, SpellcheckCollation};
+pub use self::response::{SolrDocList, SolrGroup, SolrGroupCommand};
+pub use self::response::{JsonFacetBucket, JsonFacetResult, JsonFacetBuckets, JsonFacetValue, HeatmapResult};
 
 mod http_utils;
 mod document;
+mod mapping;
 mod query;
+mod query_dsl;
+mod facet;
+mod json_facet;
+mod highlight;
+mod spellcheck;
+mod suggest;
+mod terms;
+mod more_like_this;
+mod geo;
+mod block_join;
+mod stats;
+mod group;
+mod dismax;
 mod request;
+mod atomic_update;
+mod update_request;
+mod bulk;
+mod update_stream;
 mod response;
 mod client;
+mod cursor;
@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, HashMap};
+use time::Tm;
+use document::{self, SolrDocument, SolrField, SolrValue};
+use response::SolrError;
+
+/// Conversion of a struct into SolrDocument for indexing,
+/// usually derived with `#[derive(ToSolrDocument)]` from heliotrope_derive
+pub trait ToSolrDocument {
+    fn to_solr_document(&self) -> SolrDocument;
+}
+
+/// Conversion of a SolrDocument returned from a query into a struct,
+/// usually derived with `#[derive(FromSolrDocument)]` from heliotrope_derive
+pub trait FromSolrDocument: Sized {
+    fn from_solr_document(document: &SolrDocument) -> Result<Self, SolrError>;
+}
+
+/// Conversion of a struct field into a SolrValue, `None` meaning the field is omitted
+pub trait ToSolrValue {
+    fn to_solr_value(&self) -> Option<SolrValue>;
+}
+
+/// Conversion of a SolrValue into a struct field, `None` meaning the field is missing
+pub trait FromSolrValue: Sized {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<Self, String>;
+}
+
+impl ToSolrValue for String {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        Some(SolrValue::String(self.clone()))
+    }
+}
+
+impl FromSolrValue for String {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<String, String> {
+        match value {
+            Some(&SolrValue::String(ref s)) => Ok(s.clone()),
+            v => Err(mismatch("string", v))
+        }
+    }
+}
+
+impl<'a> ToSolrValue for &'a str {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        Some(SolrValue::String(self.to_string()))
+    }
+}
+
+impl ToSolrValue for SolrValue {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        Some(self.clone())
+    }
+}
+
+impl ToSolrValue for bool {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        Some(SolrValue::Boolean(*self))
+    }
+}
+
+impl FromSolrValue for bool {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<bool, String> {
+        match value {
+            Some(&SolrValue::Boolean(b)) => Ok(b),
+            v => Err(mismatch("bool", v))
+        }
+    }
+}
+
+macro_rules! signed_value {
+    ($t:ty) => {
+        impl ToSolrValue for $t {
+            fn to_solr_value(&self) -> Option<SolrValue> {
+                Some(SolrValue::I64(*self as i64))
+            }
+        }
+
+        impl FromSolrValue for $t {
+            fn from_solr_value(value: Option<&SolrValue>) -> Result<$t, String> {
+                let i = match value {
+                    Some(&SolrValue::I64(i)) => i,
+                    Some(&SolrValue::U64(u)) if u <= i64::max_value() as u64 => u as i64,
+                    v => return Err(mismatch(stringify!($t), v))
+                };
+                if i < <$t>::min_value() as i64 || i > <$t>::max_value() as i64 {
+                    return Err(format!("{} is out of {} range", i, stringify!($t)));
+                }
+                Ok(i as $t)
+            }
+        }
+    }
+}
+
+macro_rules! unsigned_value {
+    ($t:ty) => {
+        impl ToSolrValue for $t {
+            fn to_solr_value(&self) -> Option<SolrValue> {
+                Some(SolrValue::U64(*self as u64))
+            }
+        }
+
+        impl FromSolrValue for $t {
+            fn from_solr_value(value: Option<&SolrValue>) -> Result<$t, String> {
+                let u = match value {
+                    Some(&SolrValue::U64(u)) => u,
+                    Some(&SolrValue::I64(i)) if i >= 0 => i as u64,
+                    v => return Err(mismatch(stringify!($t), v))
+                };
+                if u > <$t>::max_value() as u64 {
+                    return Err(format!("{} is out of {} range", u, stringify!($t)));
+                }
+                Ok(u as $t)
+            }
+        }
+    }
+}
+
+macro_rules! float_value {
+    ($t:ty) => {
+        impl ToSolrValue for $t {
+            fn to_solr_value(&self) -> Option<SolrValue> {
+                Some(SolrValue::F64(*self as f64))
+            }
+        }
+
+        impl FromSolrValue for $t {
+            fn from_solr_value(value: Option<&SolrValue>) -> Result<$t, String> {
+                match value {
+                    Some(&SolrValue::F64(f)) => Ok(f as $t),
+                    Some(&SolrValue::I64(i)) => Ok(i as $t),
+                    Some(&SolrValue::U64(u)) => Ok(u as $t),
+                    v => Err(mismatch(stringify!($t), v))
+                }
+            }
+        }
+    }
+}
+
+signed_value!(i8);
+signed_value!(i16);
+signed_value!(i32);
+signed_value!(i64);
+unsigned_value!(u16);
+unsigned_value!(u32);
+unsigned_value!(u64);
+float_value!(f32);
+float_value!(f64);
+
+impl ToSolrValue for Tm {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        Some(SolrValue::String(document::format_date(self)))
+    }
+}
+
+impl FromSolrValue for Tm {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<Tm, String> {
+        match value {
+            Some(&SolrValue::String(ref s)) => document::parse_date(s),
+            v => Err(mismatch("date", v))
+        }
+    }
+}
+
+impl<T: ToSolrValue> ToSolrValue for Option<T> {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        self.as_ref().and_then(|x| x.to_solr_value())
+    }
+}
+
+impl<T: FromSolrValue> FromSolrValue for Option<T> {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<Option<T>, String> {
+        match value {
+            None | Some(&SolrValue::Null) => Ok(None),
+            v => T::from_solr_value(v).map(Some)
+        }
+    }
+}
+
+/// Multi-valued fields, an empty vector is omitted
+impl<T: ToSolrValue> ToSolrValue for Vec<T> {
+    fn to_solr_value(&self) -> Option<SolrValue> {
+        let values: Vec<SolrValue> = self.iter().filter_map(|x| x.to_solr_value()).collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(SolrValue::Array(values))
+        }
+    }
+}
+
+/// Multi-valued fields, a missing field is an empty vector and a single value a vector of one
+impl<T: FromSolrValue> FromSolrValue for Vec<T> {
+    fn from_solr_value(value: Option<&SolrValue>) -> Result<Vec<T>, String> {
+        match value {
+            None | Some(&SolrValue::Null) => Ok(Vec::new()),
+            Some(&SolrValue::Array(ref values)) => {
+                let mut vec = Vec::with_capacity(values.len());
+                for v in values.iter() {
+                    vec.push(try!(T::from_solr_value(Some(v))));
+                }
+                Ok(vec)
+            },
+            v => T::from_solr_value(v).map(|x| vec!(x))
+        }
+    }
+}
+
+/// Map of dynamic fields, for example `*_s`, by the part of the field name matching `*`
+pub trait DynamicFields: Sized {
+    /// Adds fields named after pattern to the document
+    fn add_dynamic_fields(&self, pattern: &str, document: &mut SolrDocument);
+    /// Collects document fields matching pattern, except the excluded ones mapped explicitly
+    fn from_dynamic_fields(pattern: &str, excluded: &[&str], document: &SolrDocument) -> Result<Self, String>;
+}
+
+macro_rules! dynamic_fields {
+    ($map:ident) => {
+        impl<T: ToSolrValue + FromSolrValue> DynamicFields for $map<String, T> {
+            fn add_dynamic_fields(&self, pattern: &str, document: &mut SolrDocument) {
+                for (key, value) in self.iter() {
+                    if let Some(v) = value.to_solr_value() {
+                        document.fields.push(SolrField{name: pattern.replacen('*', key, 1), value: v});
+                    }
+                }
+            }
+
+            fn from_dynamic_fields(pattern: &str, excluded: &[&str], document: &SolrDocument) -> Result<$map<String, T>, String> {
+                let mut map = $map::new();
+                for name in document.field_names().into_iter().filter(|x| !excluded.contains(x)) {
+                    if let Some(key) = match_dynamic_field(pattern, name) {
+                        let merged = document.get_merged(name);
+                        let value = try!(T::from_solr_value(merged.as_ref().map(|v| &**v)).map_err(|e| format!("{}: {}", name, e)));
+                        map.insert(key.to_string(), value);
+                    }
+                }
+                Ok(map)
+            }
+        }
+    }
+}
+
+dynamic_fields!(BTreeMap);
+dynamic_fields!(HashMap);
+
+/// Returns the part of field name matching `*` of dynamic field pattern
+fn match_dynamic_field<'a>(pattern: &str, name: &'a str) -> Option<&'a str> {
+    let star = match pattern.find('*') {
+        Some(s) => s,
+        None => return None
+    };
+    let (prefix, suffix) = (&pattern[..star], &pattern[star + 1..]);
+    if name.len() > prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix) {
+        Some(&name[prefix.len()..name.len() - suffix.len()])
+    } else {
+        None
+    }
+}
+
+fn mismatch(expected: &str, value: Option<&SolrValue>) -> String {
+    match value {
+        Some(v) => format!("expected {}, found {:?}", expected, v),
+        None => "field not found".to_string()
+    }
+}
@@ -0,0 +1,230 @@
+use query::ToUrlParam;
+use dismax::FieldBoost;
+
+/// Represents MoreLikeThis options (mlt.*), shared by the search component and the /mlt handler.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoreLikeThis {
+    pub fields: Vec<String>,
+    pub min_tf: Option<u32>,
+    pub min_df: Option<u32>,
+    pub max_df: Option<u32>,
+    pub min_word_len: Option<u32>,
+    pub max_word_len: Option<u32>,
+    pub max_query_terms: Option<u32>,
+    pub count: Option<u32>,
+    pub boost: Option<bool>,
+    pub qf: Vec<FieldBoost>
+}
+
+impl MoreLikeThis {
+    /// Creates MoreLikeThis options with Solr defaults
+    pub fn new() -> MoreLikeThis {
+        MoreLikeThis::default()
+    }
+
+    /// Adds field used to find similar documents (mlt.fl)
+    pub fn add_field(&self, field: &str) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.fields.push(field.to_string());
+        mlt
+    }
+
+    /// Sets minimum frequency of a term in the source document (mlt.mintf)
+    pub fn min_tf(&self, min_tf: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.min_tf = Some(min_tf);
+        mlt
+    }
+
+    /// Sets minimum number of documents a term must occur in (mlt.mindf)
+    pub fn min_df(&self, min_df: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.min_df = Some(min_df);
+        mlt
+    }
+
+    /// Sets maximum number of documents a term may occur in (mlt.maxdf)
+    pub fn max_df(&self, max_df: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.max_df = Some(max_df);
+        mlt
+    }
+
+    /// Sets minimum word length, shorter words are ignored (mlt.minwl)
+    pub fn min_word_len(&self, min_word_len: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.min_word_len = Some(min_word_len);
+        mlt
+    }
+
+    /// Sets maximum word length, longer words are ignored (mlt.maxwl)
+    pub fn max_word_len(&self, max_word_len: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.max_word_len = Some(max_word_len);
+        mlt
+    }
+
+    /// Sets maximum number of terms in the generated query (mlt.maxqt)
+    pub fn max_query_terms(&self, max_query_terms: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.max_query_terms = Some(max_query_terms);
+        mlt
+    }
+
+    /// Sets number of similar documents returned per document (mlt.count), search component only
+    pub fn count(&self, count: u32) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.count = Some(count);
+        mlt
+    }
+
+    /// Sets whether terms of the generated query are boosted by their relevance (mlt.boost)
+    pub fn boost(&self, boost: bool) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.boost = Some(boost);
+        mlt
+    }
+
+    /// Adds query field with optional boost (mlt.qf), the field must also be in mlt.fl
+    pub fn add_qf(&self, field: &str, boost: Option<f64>) -> MoreLikeThis {
+        let mut mlt = self.clone();
+        mlt.qf.push(FieldBoost{field: field.to_string(), boost: boost});
+        mlt
+    }
+
+    /// Converts these options to URL pairs, without mlt=true
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = Vec::new();
+        if !self.fields.is_empty() {
+            vec.push(("mlt.fl".to_string(), self.fields.join(",")));
+        }
+        let params = [("mlt.mintf", self.min_tf), ("mlt.mindf", self.min_df), ("mlt.maxdf", self.max_df),
+                      ("mlt.minwl", self.min_word_len), ("mlt.maxwl", self.max_word_len),
+                      ("mlt.maxqt", self.max_query_terms), ("mlt.count", self.count)];
+        for &(param, value) in params.iter() {
+            if let Some(v) = value {
+                vec.push((param.to_string(), v.to_string()));
+            }
+        }
+        if let Some(boost) = self.boost {
+            vec.push(("mlt.boost".to_string(), boost.to_string()));
+        }
+        if !self.qf.is_empty() {
+            let fmt_fields: Vec<String> = self.qf.iter().map(|x| x.to_url_param()).collect();
+            vec.push(("mlt.qf".to_string(), fmt_fields.join(" ")));
+        }
+        vec
+    }
+}
+
+/// Represents seed of the /mlt handler request
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoreLikeThisSeed {
+    /// Similar documents are searched for the first document matching the query
+    Query(String),
+    /// Similar documents are searched for the raw text, sent as request body
+    Text(String)
+}
+
+/// Represents a request to the MoreLikeThis request handler (/mlt by default).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoreLikeThisQuery {
+    pub seed: MoreLikeThisSeed,
+    /// Path of the request handler relative to the core URL
+    pub handler: String,
+    pub options: MoreLikeThis,
+    pub fields: Vec<String>,
+    pub filters: Vec<String>,
+    pub start: Option<u64>,
+    pub rows: Option<u32>,
+    pub match_include: Option<bool>
+}
+
+impl MoreLikeThisQuery {
+    /// Creates request for documents similar to the first document matching query, for example `id:1`
+    pub fn from_query(query: &str) -> MoreLikeThisQuery {
+        MoreLikeThisQuery::new(MoreLikeThisSeed::Query(query.to_string()))
+    }
+
+    /// Creates request for documents similar to raw text
+    pub fn from_text(text: &str) -> MoreLikeThisQuery {
+        MoreLikeThisQuery::new(MoreLikeThisSeed::Text(text.to_string()))
+    }
+
+    fn new(seed: MoreLikeThisSeed) -> MoreLikeThisQuery {
+        MoreLikeThisQuery{seed: seed, handler: "mlt".to_string(), options: MoreLikeThis::new(),
+            fields: Vec::new(), filters: Vec::new(), start: None, rows: None, match_include: None}
+    }
+
+    /// Sets path of the request handler relative to the core URL, `mlt` by default
+    pub fn handler(&self, handler: &str) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.handler = handler.to_string();
+        mlt_query
+    }
+
+    /// Sets MoreLikeThis options (mlt.*)
+    pub fn options(&self, options: MoreLikeThis) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.options = options;
+        mlt_query
+    }
+
+    /// Adds field to be returned (fl)
+    pub fn add_field(&self, field: &str) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.fields.push(field.to_string());
+        mlt_query
+    }
+
+    /// Adds filter of similar documents (fq)
+    pub fn add_filter(&self, filter: &str) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.filters.push(filter.to_string());
+        mlt_query
+    }
+
+    /// Sets offset of similar documents (start)
+    pub fn start(&self, start: u64) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.start = Some(start);
+        mlt_query
+    }
+
+    /// Sets number of similar documents returned (rows)
+    pub fn rows(&self, rows: u32) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.rows = Some(rows);
+        mlt_query
+    }
+
+    /// Sets whether the seed document is returned in the match section (mlt.match.include)
+    pub fn match_include(&self, match_include: bool) -> MoreLikeThisQuery {
+        let mut mlt_query = self.clone();
+        mlt_query.match_include = Some(match_include);
+        mlt_query
+    }
+
+    /// Converts this request to a vector of URL pairs, the text seed is not included
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("wt".to_string(), "json".to_string()));
+        if let MoreLikeThisSeed::Query(ref query) = self.seed {
+            vec.push(("q".to_string(), query.clone()));
+        }
+        if !self.fields.is_empty() {
+            vec.push(("fl".to_string(), self.fields.join(",")));
+        }
+        vec.extend(self.filters.iter().map(|x| ("fq".to_string(), x.clone())));
+        if let Some(start) = self.start {
+            vec.push(("start".to_string(), start.to_string()));
+        }
+        if let Some(rows) = self.rows {
+            vec.push(("rows".to_string(), rows.to_string()));
+        }
+        vec.extend(self.options.to_pairs());
+        if let Some(match_include) = self.match_include {
+            vec.push(("mlt.match.include".to_string(), match_include.to_string()));
+        }
+        vec
+    }
+}
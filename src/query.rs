@@ -1,3 +1,14 @@
+use facet::{FacetField, FacetOptions, FacetSort, FacetRange, FacetInterval, FacetPivot};
+use json_facet::{self, JsonFacet};
+use highlight::Highlighting;
+use spellcheck::Spellcheck;
+use more_like_this::MoreLikeThis;
+use stats::StatsField;
+use block_join::ChildTransformer;
+use group::{Grouping, Collapse, Expand};
+use dismax::{QueryParser, DisMax};
+use query_dsl::ToQueryString;
+
 static DEFAULT_START: u64 = 0;
 static DEFAULT_ROWS: u32 = 10;
 
@@ -12,25 +23,78 @@ pub trait ToUrlParam {
 #[derive(Clone)]
 pub struct SolrQuery {
     query: String,
+    def_type: Option<QueryParser>,
+    dismax: Option<DisMax>,
     fields: Option<Vec<String>>,
     filters: Option<Vec<String>>,
     sorts: Option<Vec<SortClause>>,
     start: u64,
-    rows: u32
+    rows: u32,
+    cursor_mark: Option<String>,
+    facet_fields: Option<Vec<FacetField>>,
+    facet_ranges: Option<Vec<FacetRange>>,
+    facet_intervals: Option<Vec<FacetInterval>>,
+    facet_pivots: Option<Vec<FacetPivot>>,
+    facet_pivot_mincount: Option<u32>,
+    facet_options: FacetOptions,
+    json_facets: Option<Vec<(String, JsonFacet)>>,
+    highlighting: Option<Highlighting>,
+    grouping: Option<Grouping>,
+    collapse: Option<Collapse>,
+    expand: Option<Expand>,
+    spellcheck: Option<Spellcheck>,
+    more_like_this: Option<MoreLikeThis>,
+    stats_fields: Option<Vec<StatsField>>
 }
 
 impl SolrQuery {
-    /// Creates a new SolrQuery with only query term inside it
-    pub fn new(query: &str) -> SolrQuery {
-        SolrQuery { query: query.to_string(),
+    /// Creates a new SolrQuery with only query term inside it.
+    /// Accepts either a raw query string or a typed `Query`, which is escaped.
+    pub fn new<Q: ToQueryString + ?Sized>(query: &Q) -> SolrQuery {
+        SolrQuery { query: query.to_query_string(),
+            def_type: None,
+            dismax: None,
             fields: None,
             filters: None,
             sorts: None,
             start: 0,
-            rows: DEFAULT_ROWS }
+            rows: DEFAULT_ROWS,
+            cursor_mark: None,
+            facet_fields: None,
+            facet_ranges: None,
+            facet_intervals: None,
+            facet_pivots: None,
+            facet_pivot_mincount: None,
+            facet_options: FacetOptions::default(),
+            json_facets: None,
+            highlighting: None,
+            grouping: None,
+            collapse: None,
+            expand: None,
+            spellcheck: None,
+            more_like_this: None,
+            stats_fields: None }
 
     }
 
+    /// Sets query parser used for the main query (defType)
+    pub fn def_type(&self, def_type: QueryParser) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.def_type = Some(def_type);
+        solr_query
+    }
+
+    /// Sets DisMax/eDisMax query parser parameters.
+    /// When query parser is not set with `def_type`, eDisMax is used.
+    pub fn dismax(&self, dismax: DisMax) -> SolrQuery {
+        let mut solr_query = self.clone();
+        if solr_query.def_type.is_none() {
+            solr_query.def_type = Some(QueryParser::EDisMax);
+        }
+        solr_query.dismax = Some(dismax);
+        solr_query
+    }
+
     /// Adds field (l) to the list of returned fields
     pub fn add_field(&self, field: &str) -> SolrQuery {
         let mut fields = self.fields.clone();
@@ -46,6 +110,11 @@ impl SolrQuery {
         solr_query
     }
 
+    /// Adds [child] transformer to the list of returned fields, returning nested child documents
+    pub fn add_child_transformer(&self, transformer: ChildTransformer) -> SolrQuery {
+        self.add_field(&transformer.to_url_param())
+    }
+
     /// Sets fields (fl) as the list of returned fields.
     /// The already set fields are overwritten.
     pub fn set_fields(&self, fields: &[&str]) -> SolrQuery {
@@ -56,21 +125,27 @@ impl SolrQuery {
         solr_query
     }
 
-    /// Adds query filter (fq)
-    pub fn add_filter(&self, filter: &str) -> SolrQuery {
+    /// Adds query filter (fq), either a raw query string or a typed `Query`
+    pub fn add_filter<Q: ToQueryString + ?Sized>(&self, filter: &Q) -> SolrQuery {
         let mut filters = self.filters.clone();
         filters = match filters {
             Some(mut f) => {
-                f.push(filter.to_string());
+                f.push(filter.to_query_string());
                 Some(f)
             },
-            None => Some(vec!(filter.to_string()))
+            None => Some(vec!(filter.to_query_string()))
         };
         let mut solr_query = self.clone();
         solr_query.filters = filters;
         solr_query
     }
 
+    /// Adds query filter (fq) tagged with tag ({!tag=...}).
+    /// Tagged filters can be excluded when faceting, see `FacetField::exclude`.
+    pub fn add_tagged_filter<Q: ToQueryString + ?Sized>(&self, tag: &str, filter: &Q) -> SolrQuery {
+        self.add_filter(&tag_filter(tag, &filter.to_query_string()))
+    }
+
     /// Sets query filters (fq)
     /// Already existing filters are overwritten.
     pub fn set_filters(&self, filters: &[&str]) -> SolrQuery {
@@ -129,12 +204,274 @@ impl SolrQuery {
         solr_query
     }
 
+    /// Sets cursor mark (cursorMark) for deep paging.
+    /// Use `*` for the first page and `next_cursor_mark` of the previous response afterwards.
+    /// The sort must include the unique key field and start must be 0.
+    pub fn cursor_mark(&self, cursor_mark: &str) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.cursor_mark = Some(cursor_mark.to_string());
+        solr_query
+    }
+
+    /// Returns true when the query is sorted by a given field
+    pub fn is_sorted_by(&self, field: &str) -> bool {
+        match self.sorts {
+            Some(ref s) => s.iter().any(|x| x.field == field),
+            None => false
+        }
+    }
+
+    /// Returns initial offset (zero based)
+    pub fn get_start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns cursor mark (cursorMark), `None` when not set
+    pub fn get_cursor_mark(&self) -> Option<&str> {
+        self.cursor_mark.as_ref().map(|x| x.as_ref())
+    }
+
+    /// Adds field facet (facet.field).
+    /// Faceting is enabled automatically.
+    pub fn add_facet_field(&self, facet: FacetField) -> SolrQuery {
+        let mut facet_fields = self.facet_fields.clone();
+        facet_fields = match facet_fields {
+            Some(mut f) => {
+                f.push(facet);
+                Some(f)
+            },
+            None => Some(vec!(facet))
+        };
+        let mut solr_query = self.clone();
+        solr_query.facet_fields = facet_fields;
+        solr_query
+    }
+
+    /// Sets field facets (facet.field).
+    /// Already existing field facets are overwritten.
+    pub fn set_facet_fields(&self, facets: &[FacetField]) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_fields = Some(facets.to_vec());
+        solr_query
+    }
+
+    /// Adds range facet (facet.range).
+    /// Faceting is enabled automatically.
+    pub fn add_facet_range(&self, facet: FacetRange) -> SolrQuery {
+        let mut facet_ranges = self.facet_ranges.clone();
+        facet_ranges = match facet_ranges {
+            Some(mut f) => {
+                f.push(facet);
+                Some(f)
+            },
+            None => Some(vec!(facet))
+        };
+        let mut solr_query = self.clone();
+        solr_query.facet_ranges = facet_ranges;
+        solr_query
+    }
+
+    /// Sets range facets (facet.range).
+    /// Already existing range facets are overwritten.
+    pub fn set_facet_ranges(&self, facets: &[FacetRange]) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_ranges = Some(facets.to_vec());
+        solr_query
+    }
+
+    /// Adds interval facet (facet.interval).
+    /// Faceting is enabled automatically.
+    pub fn add_facet_interval(&self, facet: FacetInterval) -> SolrQuery {
+        let mut facet_intervals = self.facet_intervals.clone();
+        facet_intervals = match facet_intervals {
+            Some(mut f) => {
+                f.push(facet);
+                Some(f)
+            },
+            None => Some(vec!(facet))
+        };
+        let mut solr_query = self.clone();
+        solr_query.facet_intervals = facet_intervals;
+        solr_query
+    }
+
+    /// Sets interval facets (facet.interval).
+    /// Already existing interval facets are overwritten.
+    pub fn set_facet_intervals(&self, facets: &[FacetInterval]) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_intervals = Some(facets.to_vec());
+        solr_query
+    }
+
+    /// Adds pivot facet (facet.pivot).
+    /// Faceting is enabled automatically.
+    pub fn add_facet_pivot(&self, facet: FacetPivot) -> SolrQuery {
+        let mut facet_pivots = self.facet_pivots.clone();
+        facet_pivots = match facet_pivots {
+            Some(mut f) => {
+                f.push(facet);
+                Some(f)
+            },
+            None => Some(vec!(facet))
+        };
+        let mut solr_query = self.clone();
+        solr_query.facet_pivots = facet_pivots;
+        solr_query
+    }
+
+    /// Sets pivot facets (facet.pivot).
+    /// Already existing pivot facets are overwritten.
+    pub fn set_facet_pivots(&self, facets: &[FacetPivot]) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_pivots = Some(facets.to_vec());
+        solr_query
+    }
+
+    /// Sets minimum count for a pivot value to be returned (facet.pivot.mincount)
+    pub fn facet_pivot_mincount(&self, mincount: u32) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_pivot_mincount = Some(mincount);
+        solr_query
+    }
+
+    /// Sets default maximum number of terms returned per field facet (facet.limit).
+    /// Negative value means no limit.
+    pub fn facet_limit(&self, limit: i32) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_options.limit = Some(limit);
+        solr_query
+    }
+
+    /// Sets default minimum count for a term to be returned (facet.mincount)
+    pub fn facet_mincount(&self, mincount: u32) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_options.mincount = Some(mincount);
+        solr_query
+    }
+
+    /// Sets default ordering of facet terms (facet.sort)
+    pub fn facet_sort(&self, sort: FacetSort) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_options.sort = Some(sort);
+        solr_query
+    }
+
+    /// Limits facet terms to those starting with prefix (facet.prefix)
+    pub fn facet_prefix(&self, prefix: &str) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_options.prefix = Some(prefix.to_string());
+        solr_query
+    }
+
+    /// Enables counting of documents without a value in faceted fields (facet.missing)
+    pub fn facet_missing(&self, missing: bool) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.facet_options.missing = Some(missing);
+        solr_query
+    }
+
+    /// Adds named facet or aggregation of the JSON Facet API (json.facet)
+    pub fn add_json_facet<F: Into<JsonFacet>>(&self, name: &str, facet: F) -> SolrQuery {
+        let mut json_facets = self.json_facets.clone();
+        let named_facet = (name.to_string(), facet.into());
+        json_facets = match json_facets {
+            Some(mut f) => {
+                f.push(named_facet);
+                Some(f)
+            },
+            None => Some(vec!(named_facet))
+        };
+        let mut solr_query = self.clone();
+        solr_query.json_facets = json_facets;
+        solr_query
+    }
+
+    /// Sets named facets of the JSON Facet API (json.facet).
+    /// Already existing JSON facets are overwritten.
+    pub fn set_json_facets(&self, facets: &[(&str, JsonFacet)]) -> SolrQuery {
+        let mut new_facets = Vec::with_capacity(facets.len());
+        new_facets.extend(facets.iter().map(|&(name, ref facet)| (name.to_string(), facet.clone())));
+        let mut solr_query = self.clone();
+        solr_query.json_facets = Some(new_facets);
+        solr_query
+    }
+
+    /// Enables highlighting (hl) with given options
+    pub fn highlight(&self, highlighting: Highlighting) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.highlighting = Some(highlighting);
+        solr_query
+    }
+
+    /// Enables result grouping (group) with given options
+    pub fn group(&self, grouping: Grouping) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.grouping = Some(grouping);
+        solr_query
+    }
+
+    /// Collapses results to a single document per field value ({!collapse} filter)
+    pub fn collapse(&self, collapse: Collapse) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.collapse = Some(collapse);
+        solr_query
+    }
+
+    /// Enables returning documents removed by collapse (expand)
+    pub fn expand(&self, expand: Expand) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.expand = Some(expand);
+        solr_query
+    }
+
+    /// Enables spellchecking (spellcheck) with given options
+    pub fn spellcheck(&self, spellcheck: Spellcheck) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.spellcheck = Some(spellcheck);
+        solr_query
+    }
+
+    /// Enables returning similar documents for each result (mlt) with given options
+    pub fn more_like_this(&self, more_like_this: MoreLikeThis) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.more_like_this = Some(more_like_this);
+        solr_query
+    }
+
+    /// Adds field statistics (stats.field), enabling the stats component
+    pub fn add_stats_field(&self, stats_field: StatsField) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.stats_fields = match solr_query.stats_fields {
+            Some(mut s) => {
+                s.push(stats_field);
+                Some(s)
+            },
+            None => Some(vec!(stats_field))
+        };
+        solr_query
+    }
+
+    /// Sets field statistics (stats.field)
+    /// Already existing field statistics are overwritten.
+    pub fn set_stats_fields(&self, stats_fields: &[StatsField]) -> SolrQuery {
+        let mut solr_query = self.clone();
+        solr_query.stats_fields = Some(stats_fields.to_vec());
+        solr_query
+    }
+
     /// Converts this query to a vector of pairs, suitable for URL percent encoding
     pub fn to_pairs(&self) -> Vec<(String, String)> {
         // usually will be wt, q and something else
         let mut vec = Vec::with_capacity(3);
         vec.push(("wt".to_string(), "json".to_string()));
         vec.push(("q".to_string(), self.query.to_string()));
+        if let Some(ref def_type) = self.def_type {
+            vec.push(("defType".to_string(), def_type.to_url_param()));
+        }
+        match self.dismax {
+            Some(ref d) => vec.extend(d.to_pairs()),
+            _ => ()
+        }
         match self.fields {
             Some(ref f) => {
                 let mut fmt_fields = String::new();
@@ -157,6 +494,11 @@ impl SolrQuery {
             _ => ()
         }
 
+        match self.collapse {
+            Some(ref c) => vec.push(("fq".to_string(), c.to_url_param())),
+            _ => ()
+        }
+
         match self.sorts {
             Some(ref s) => {
                 let sort_url_params: Vec<String> = s.clone()
@@ -182,8 +524,117 @@ impl SolrQuery {
         if self.rows != DEFAULT_ROWS {
             vec.push(("rows".to_string(), self.rows.to_string()));
         }
+
+        if let Some(ref cursor_mark) = self.cursor_mark {
+            vec.push(("cursorMark".to_string(), cursor_mark.clone()));
+        }
+
+        if self.is_faceted() {
+            vec.push(("facet".to_string(), "true".to_string()));
+            vec.extend(self.facet_options.to_pairs(""));
+        }
+
+        match self.facet_fields {
+            Some(ref f) => {
+                for facet in f.iter() {
+                    vec.extend(facet.to_pairs());
+                }
+            },
+            _ => ()
+        }
+
+        match self.facet_ranges {
+            Some(ref f) => {
+                for facet in f.iter() {
+                    vec.extend(facet.to_pairs());
+                }
+            },
+            _ => ()
+        }
+
+        match self.facet_intervals {
+            Some(ref f) => {
+                for facet in f.iter() {
+                    vec.extend(facet.to_pairs());
+                }
+            },
+            _ => ()
+        }
+
+        match self.facet_pivots {
+            Some(ref f) => {
+                for facet in f.iter() {
+                    vec.extend(facet.to_pairs());
+                }
+                if let Some(mincount) = self.facet_pivot_mincount {
+                    vec.push(("facet.pivot.mincount".to_string(), mincount.to_string()));
+                }
+            },
+            _ => ()
+        }
+
+        match self.json_facets {
+            Some(ref f) => {
+                vec.push(("json.facet".to_string(), json_facet::facets_to_json(f).to_string()));
+            },
+            _ => ()
+        }
+
+        match self.highlighting {
+            Some(ref hl) => vec.extend(hl.to_pairs()),
+            _ => ()
+        }
+
+        match self.grouping {
+            Some(ref g) => vec.extend(g.to_pairs()),
+            _ => ()
+        }
+
+        match self.expand {
+            Some(ref e) => vec.extend(e.to_pairs()),
+            _ => ()
+        }
+
+        match self.spellcheck {
+            Some(ref s) => vec.extend(s.to_pairs()),
+            _ => ()
+        }
+
+        match self.stats_fields {
+            Some(ref fields) => {
+                vec.push(("stats".to_string(), "true".to_string()));
+                for f in fields.iter() {
+                    vec.extend(f.to_pairs());
+                }
+            },
+            _ => ()
+        }
+
+        match self.more_like_this {
+            Some(ref mlt) => {
+                vec.push(("mlt".to_string(), "true".to_string()));
+                vec.extend(mlt.to_pairs());
+            },
+            _ => ()
+        }
         vec
     }
+
+    fn is_faceted(&self) -> bool {
+        self.facet_fields.is_some() || self.facet_ranges.is_some() ||
+            self.facet_intervals.is_some() || self.facet_pivots.is_some()
+    }
+}
+
+/// Adds tag to filter local params, merging with already existing ones,
+/// for example `{!term f=type}Book` becomes `{!term f=type tag=type}Book`
+fn tag_filter(tag: &str, filter: &str) -> String {
+    if filter.starts_with("{!") {
+        if let Some(end) = filter.find('}') {
+            return format!("{} tag={}{}", &filter[..end], tag, &filter[end..]);
+        }
+    }
+    format!("{{!tag={}}}{}", tag, filter)
 }
 
 /// Represents sort ordering for a field
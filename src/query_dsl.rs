@@ -0,0 +1,334 @@
+use std::fmt;
+
+/// Characters with special meaning in Lucene/Solr query syntax
+static SPECIAL_CHARS: &'static str = "+-!(){}[]^\"~*?:\\/&|;";
+
+/// Boolean operators, which are not matched as terms unless quoted
+static KEYWORDS: [&'static str; 3] = ["AND", "OR", "NOT"];
+
+/// Escapes special characters and whitespace, so user input is matched literally.
+/// Bare `AND`, `OR` and `NOT` and empty values are quoted instead.
+pub fn escape(value: &str) -> String {
+    if value.is_empty() || KEYWORDS.contains(&value) {
+        return format!("\"{}\"", value);
+    }
+    escape_chars(value)
+}
+
+/// Escapes special characters and whitespace, also used for field names
+fn escape_chars(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if SPECIAL_CHARS.contains(c) || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes value to be used inside a quoted phrase
+fn escape_phrase(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes wildcard pattern, keeping `*` and `?` as wildcards
+fn escape_wildcard(pattern: &str) -> String {
+    if pattern.is_empty() || KEYWORDS.contains(&pattern) {
+        return format!("\"{}\"", pattern);
+    }
+    let mut escaped = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if (SPECIAL_CHARS.contains(c) && c != '*' && c != '?') || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Formats range bound, quoting values with special characters, `None` meaning unbounded
+fn format_bound(bound: &Option<String>) -> String {
+    match *bound {
+        Some(ref b) if b.chars().any(|c| SPECIAL_CHARS.contains(c) || c.is_whitespace()) => format!("\"{}\"", escape_phrase(b)),
+        Some(ref b) => b.clone(),
+        None => "*".to_string()
+    }
+}
+
+/// Formats local param value, quoting it when needed
+pub fn format_param_value(value: &str) -> String {
+    if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c == '}' || c == '=' || c == '\'' || c == '"') {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Represents local params prefix, for example `{!parent which=type:product}`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalParams {
+    /// Query parser type, for example `parent` or `geofilt`
+    pub parser: Option<String>,
+    pub params: Vec<(String, String)>
+}
+
+impl LocalParams {
+    /// Creates local params with optional query parser type
+    pub fn new(parser: Option<&str>) -> LocalParams {
+        LocalParams{parser: parser.map(|x| x.to_string()), params: Vec::new()}
+    }
+
+    /// Adds key=value parameter, the value is quoted when needed
+    pub fn param(&self, key: &str, value: &str) -> LocalParams {
+        let mut local_params = self.clone();
+        local_params.params.push((key.to_string(), value.to_string()));
+        local_params
+    }
+}
+
+impl fmt::Display for LocalParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::with_capacity(self.params.len() + 1);
+        if let Some(ref parser) = self.parser {
+            parts.push(parser.clone());
+        }
+        for &(ref k, ref v) in self.params.iter() {
+            parts.push(format!("{}={}", k, format_param_value(v)));
+        }
+        write!(f, "{{!{}}}", parts.join(" "))
+    }
+}
+
+/// Represents a query in Lucene/Solr syntax.
+/// Values are escaped when rendered, so user input can be passed safely.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Query {
+    /// Matches all documents (*:*)
+    All,
+    /// Single term, in the default field when field is `None`
+    Term(Option<String>, String),
+    Phrase(Option<String>, String),
+    /// Field, lower and upper bounds (`None` meaning unbounded), include lower, include upper
+    Range(String, Option<String>, Option<String>, bool, bool),
+    /// Pattern with `*` and `?` wildcards
+    Wildcard(Option<String>, String),
+    /// Term with maximum edit distance
+    Fuzzy(Option<String>, String, u8),
+    /// Phrase with maximum distance between terms
+    Proximity(Option<String>, String, u32),
+    Boolean(BooleanQuery),
+    Boost(Box<Query>, f64),
+    /// Query scoped to a field, for example title:(dragon OR rider)
+    Field(String, Box<Query>),
+    /// Query prefixed with local params, the query may be omitted, for example for {!geofilt}
+    LocalParams(LocalParams, Option<Box<Query>>),
+    /// Query string passed as is, without escaping
+    Raw(String)
+}
+
+impl Query {
+    /// Matches all documents (*:*)
+    pub fn all() -> Query {
+        Query::All
+    }
+
+    /// Matches documents containing term in the field
+    pub fn term(field: &str, value: &str) -> Query {
+        Query::Term(Some(field.to_string()), value.to_string())
+    }
+
+    /// Matches documents containing term in the default field
+    pub fn text(value: &str) -> Query {
+        Query::Term(None, value.to_string())
+    }
+
+    /// Matches documents containing phrase in the field
+    pub fn phrase(field: &str, value: &str) -> Query {
+        Query::Phrase(Some(field.to_string()), value.to_string())
+    }
+
+    /// Matches documents with field value between lower and upper, `None` meaning unbounded
+    pub fn range(field: &str, lower: Option<&str>, upper: Option<&str>, include_lower: bool, include_upper: bool) -> Query {
+        Query::Range(field.to_string(), lower.map(|x| x.to_string()), upper.map(|x| x.to_string()),
+                     include_lower, include_upper)
+    }
+
+    /// Matches documents with terms matching pattern with `*` and `?` wildcards
+    pub fn wildcard(field: &str, pattern: &str) -> Query {
+        Query::Wildcard(Some(field.to_string()), pattern.to_string())
+    }
+
+    /// Matches documents with terms similar to value, within edit distance (0 to 2)
+    pub fn fuzzy(field: &str, value: &str, distance: u8) -> Query {
+        Query::Fuzzy(Some(field.to_string()), value.to_string(), distance)
+    }
+
+    /// Matches documents with terms of the phrase within slop positions of each other
+    pub fn proximity(field: &str, phrase: &str, slop: u32) -> Query {
+        Query::Proximity(Some(field.to_string()), phrase.to_string(), slop)
+    }
+
+    /// Scopes query to a field
+    pub fn field(field: &str, query: Query) -> Query {
+        Query::Field(field.to_string(), Box::new(query))
+    }
+
+    /// Prefixes query with local params, query may be omitted
+    pub fn local_params(local_params: LocalParams, query: Option<Query>) -> Query {
+        Query::LocalParams(local_params, query.map(Box::new))
+    }
+
+    /// Matches parent documents of children matching query ({!parent which=...}),
+    /// `which` selecting all parent documents, for example `type:product`
+    pub fn parent(which: &str, query: Query) -> Query {
+        Query::local_params(LocalParams::new(Some("parent")).param("which", which), Some(query))
+    }
+
+    /// Matches child documents of parents matching query ({!child of=...}),
+    /// `of` selecting all parent documents, for example `type:product`
+    pub fn child(of: &str, query: Query) -> Query {
+        Query::local_params(LocalParams::new(Some("child")).param("of", of), Some(query))
+    }
+
+    /// Passes query string as is, without escaping
+    pub fn raw(query: &str) -> Query {
+        Query::Raw(query.to_string())
+    }
+
+    /// Boosts query score
+    pub fn boost(self, boost: f64) -> Query {
+        Query::Boost(Box::new(self), boost)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Query::All => write!(f, "*:*"),
+            Query::Term(ref field, ref value) => write!(f, "{}{}", field_prefix(field), escape(value)),
+            Query::Phrase(ref field, ref value) => write!(f, "{}\"{}\"", field_prefix(field), escape_phrase(value)),
+            Query::Range(ref field, ref lower, ref upper, include_lower, include_upper) =>
+                write!(f, "{}:{}{} TO {}{}", escape_chars(field),
+                       if include_lower { "[" } else { "{" },
+                       format_bound(lower), format_bound(upper),
+                       if include_upper { "]" } else { "}" }),
+            Query::Wildcard(ref field, ref pattern) => write!(f, "{}{}", field_prefix(field), escape_wildcard(pattern)),
+            Query::Fuzzy(ref field, ref value, distance) => write!(f, "{}{}~{}", field_prefix(field), escape(value), distance),
+            Query::Proximity(ref field, ref phrase, slop) => write!(f, "{}\"{}\"~{}", field_prefix(field), escape_phrase(phrase), slop),
+            Query::Boolean(ref b) => write!(f, "{}", b),
+            Query::Boost(ref query, boost) => match **query {
+                Query::Boolean(_) | Query::Field(_, _) | Query::Raw(_) | Query::LocalParams(_, _) => write!(f, "({})^{}", query, boost),
+                _ => write!(f, "{}^{}", query, boost)
+            },
+            Query::Field(ref field, ref query) => match **query {
+                Query::Boolean(_) => write!(f, "{}:{}", escape_chars(field), query),
+                _ => write!(f, "{}:({})", escape_chars(field), query)
+            },
+            Query::LocalParams(ref local_params, Some(ref query)) => write!(f, "{}{}", local_params, query),
+            Query::LocalParams(ref local_params, None) => write!(f, "{}", local_params),
+            Query::Raw(ref query) => write!(f, "{}", query)
+        }
+    }
+}
+
+fn field_prefix(field: &Option<String>) -> String {
+    match *field {
+        Some(ref f) => format!("{}:", escape_chars(f)),
+        None => String::new()
+    }
+}
+
+/// Represents boolean combination of queries.
+/// Should clauses rely on the default operator being OR.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BooleanQuery {
+    pub must: Vec<Query>,
+    pub should: Vec<Query>,
+    pub must_not: Vec<Query>
+}
+
+impl BooleanQuery {
+    /// Creates empty boolean query
+    pub fn new() -> BooleanQuery {
+        BooleanQuery::default()
+    }
+
+    /// Adds clause every matching document must match (+)
+    pub fn must(&self, query: Query) -> BooleanQuery {
+        let mut boolean_query = self.clone();
+        boolean_query.must.push(query);
+        boolean_query
+    }
+
+    /// Adds optional clause
+    pub fn should(&self, query: Query) -> BooleanQuery {
+        let mut boolean_query = self.clone();
+        boolean_query.should.push(query);
+        boolean_query
+    }
+
+    /// Adds clause no matching document may match (-)
+    pub fn must_not(&self, query: Query) -> BooleanQuery {
+        let mut boolean_query = self.clone();
+        boolean_query.must_not.push(query);
+        boolean_query
+    }
+}
+
+impl From<BooleanQuery> for Query {
+    fn from(boolean_query: BooleanQuery) -> Query {
+        Query::Boolean(boolean_query)
+    }
+}
+
+impl fmt::Display for BooleanQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut clauses = Vec::with_capacity(self.must.len() + self.should.len() + self.must_not.len() + 1);
+        // purely negative queries don't match anything when nested
+        if self.must.is_empty() && self.should.is_empty() && !self.must_not.is_empty() {
+            clauses.push("*:*".to_string());
+        }
+        clauses.extend(self.must.iter().map(|x| format!("+{}", clause(x))));
+        clauses.extend(self.should.iter().map(clause));
+        clauses.extend(self.must_not.iter().map(|x| format!("-{}", clause(x))));
+        write!(f, "({})", clauses.join(" "))
+    }
+}
+
+/// Renders boolean clause, wrapping queries which can't be prefixed with +/- directly
+fn clause(query: &Query) -> String {
+    match *query {
+        Query::LocalParams(_, _) | Query::Raw(_) => format!("({})", query),
+        _ => query.to_string()
+    }
+}
+
+/// Conversion to a query string, implemented for plain strings and typed queries
+pub trait ToQueryString {
+    fn to_query_string(&self) -> String;
+}
+
+impl ToQueryString for str {
+    fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToQueryString for String {
+    fn to_query_string(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ToQueryString for Query {
+    fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToQueryString for BooleanQuery {
+    fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
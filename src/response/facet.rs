@@ -0,0 +1,438 @@
+use std::cmp::Ordering;
+use rustc_serialize::json::Json;
+
+/// Single facet term with the number of matching documents
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetCount {
+    pub term: String,
+    pub count: u64
+}
+
+/// Facet counts for a single field (facet.field), in the order returned by Solr
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetFieldCounts {
+    /// Field name or the key set for the facet
+    pub field: String,
+    pub counts: Vec<FacetCount>,
+    /// Number of documents without a value in the field.
+    /// Only present when facet.missing was requested.
+    pub missing: Option<u64>
+}
+
+/// Typed value of a range or interval bound
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeValue {
+    Int(i64),
+    Float(f64),
+    /// Date in Solr format, for example 2015-05-01T00:00:00Z
+    Date(String)
+}
+
+/// Typed gap of a range facet
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeGap {
+    Int(i64),
+    Float(f64),
+    /// Date math expression, for example +1DAY
+    DateMath(String)
+}
+
+/// Single range facet bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetRangeBucket {
+    /// Lower bound of the bucket
+    pub start: RangeValue,
+    /// Upper bound of the bucket.
+    /// Only computed for numeric ranges, date math gaps are not evaluated.
+    pub end: Option<RangeValue>,
+    pub count: u64
+}
+
+/// Range facet counts for a single field (facet.range)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetRangeCounts {
+    /// Field name or the key set for the facet
+    pub field: String,
+    /// Buckets in ascending order
+    pub buckets: Vec<FacetRangeBucket>,
+    pub start: RangeValue,
+    pub end: RangeValue,
+    pub gap: RangeGap,
+    /// Count of documents lower than start, when requested with facet.range.other
+    pub before: Option<u64>,
+    /// Count of documents greater than end, when requested with facet.range.other
+    pub after: Option<u64>,
+    /// Count of documents between start and end, when requested with facet.range.other
+    pub between: Option<u64>
+}
+
+/// Bounds of a facet interval, `None` meaning unbounded
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalBounds {
+    pub start: Option<RangeValue>,
+    pub end: Option<RangeValue>,
+    pub include_start: bool,
+    pub include_end: bool
+}
+
+/// Count of a single facet interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetIntervalCount {
+    /// Interval as returned by Solr, either the interval set itself or its key
+    pub key: String,
+    /// Parsed bounds, `None` when the interval was labelled with a custom key
+    pub bounds: Option<IntervalBounds>,
+    pub count: u64
+}
+
+/// Interval facet counts for a single field (facet.interval)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetIntervalCounts {
+    /// Field name or the key set for the facet
+    pub field: String,
+    /// Intervals ordered by lower bound, keyed intervals last
+    pub counts: Vec<FacetIntervalCount>
+}
+
+/// Single value of a pivot facet with counts for the next pivot field
+#[derive(Debug, Clone, PartialEq)]
+pub struct PivotEntry {
+    pub field: String,
+    /// Field value, `None` for documents without a value
+    pub value: Option<String>,
+    pub count: u64,
+    /// Nested entries for the next field, empty on the last level
+    pub pivot: Vec<PivotEntry>
+}
+
+/// Pivot facet counts (facet.pivot)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetPivotCounts {
+    /// Comma separated pivot fields or the key set for the pivot
+    pub key: String,
+    pub entries: Vec<PivotEntry>
+}
+
+/// Parsed facet_counts section of the query response
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolrFacetCounts {
+    /// Field facets, ordered by field name
+    pub fields: Vec<FacetFieldCounts>,
+    /// Range facets, ordered by field name
+    pub ranges: Vec<FacetRangeCounts>,
+    /// Interval facets, ordered by field name
+    pub intervals: Vec<FacetIntervalCounts>,
+    /// Pivot facets, ordered by key
+    pub pivots: Vec<FacetPivotCounts>
+}
+
+/* Example JSON of facet_counts:
+```ignore
+"facet_counts": {
+  "facet_queries": {},
+  "facet_fields": {
+    "city": ["London", 12, "Paris", 3, null, 1]
+  },
+  "facet_ranges": {
+    "price": {
+      "counts": ["0.0", 5, "10.0", 3],
+      "gap": 10.0, "start": 0.0, "end": 20.0,
+      "before": 0, "after": 2, "between": 8
+    }
+  },
+  "facet_intervals": {
+    "price": {"[0,10)": 5, "[10,*]": 5}
+  },
+  "facet_pivot": {
+    "cat,brand": [
+      {"field": "cat", "value": "books", "count": 10,
+       "pivot": [{"field": "brand", "value": "acme", "count": 3}]}
+    ]
+  }
+}
+```
+*/
+impl SolrFacetCounts {
+    /// Finds facet counts for a given field
+    pub fn field(&self, name: &str) -> Option<&FacetFieldCounts> {
+        self.fields.iter().find(|f| f.field == name)
+    }
+
+    /// Finds range facet counts for a given field
+    pub fn range(&self, name: &str) -> Option<&FacetRangeCounts> {
+        self.ranges.iter().find(|f| f.field == name)
+    }
+
+    /// Finds interval facet counts for a given field
+    pub fn interval(&self, name: &str) -> Option<&FacetIntervalCounts> {
+        self.intervals.iter().find(|f| f.field == name)
+    }
+
+    /// Finds pivot facet counts by comma separated fields or key
+    pub fn pivot(&self, key: &str) -> Option<&FacetPivotCounts> {
+        self.pivots.iter().find(|f| f.key == key)
+    }
+
+    /// Deserializes SolrFacetCounts from facet_counts JSON
+    pub fn from_json(json: &Json) -> Result<SolrFacetCounts, String> {
+        let mut facet_counts = SolrFacetCounts{fields: Vec::new(), ranges: Vec::new(), intervals: Vec::new(), pivots: Vec::new()};
+        match json.find("facet_fields") {
+            Some(&Json::Object(ref tm)) => {
+                for (field, counts_json) in tm.iter() {
+                    facet_counts.fields.push(try!(parse_field_counts(field, counts_json)));
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (facet_counts): facet_fields is not an object".to_string()),
+            None => ()
+        }
+        match json.find("facet_ranges") {
+            Some(&Json::Object(ref tm)) => {
+                for (field, range_json) in tm.iter() {
+                    facet_counts.ranges.push(try!(parse_range_counts(field, range_json)));
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (facet_counts): facet_ranges is not an object".to_string()),
+            None => ()
+        }
+        match json.find("facet_intervals") {
+            Some(&Json::Object(ref tm)) => {
+                for (field, intervals_json) in tm.iter() {
+                    facet_counts.intervals.push(try!(parse_interval_counts(field, intervals_json)));
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (facet_counts): facet_intervals is not an object".to_string()),
+            None => ()
+        }
+        match json.find("facet_pivot") {
+            Some(&Json::Object(ref tm)) => {
+                for (key, entries_json) in tm.iter() {
+                    facet_counts.pivots.push(FacetPivotCounts{key: key.clone(), entries: try!(parse_pivot_entries(entries_json))});
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (facet_counts): facet_pivot is not an object".to_string()),
+            None => ()
+        }
+        Ok(facet_counts)
+    }
+}
+
+/// Parses flat [term, count, term, count, ...] list
+fn parse_field_counts(field: &str, json: &Json) -> Result<FacetFieldCounts, String> {
+    let mut field_counts = FacetFieldCounts{field: field.to_string(), counts: Vec::new(), missing: None};
+    match json {
+        & Json::Array(ref flat) => {
+            for pair in flat.chunks(2) {
+                if pair.len() != 2 {
+                    return Err(format!("SolrQueryResponse JSON parsing error (facet_fields): odd number of elements for {}", field));
+                }
+                let count = match pair[1].as_u64() {
+                    Some(c) => c,
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (facet_fields): count is not a number for {}", field))
+                };
+                match pair[0] {
+                    Json::Null => field_counts.missing = Some(count),
+                    Json::String(ref term) => field_counts.counts.push(FacetCount{term: term.clone(), count: count}),
+                    ref other => field_counts.counts.push(FacetCount{term: other.to_string(), count: count})
+                }
+            }
+            Ok(field_counts)
+        },
+        _ => Err(format!("SolrQueryResponse JSON parsing error (facet_fields): {} is not a JSON list", field))
+    }
+}
+
+/// Parses range facet object: counts list, start, end, gap and optional other counts
+fn parse_range_counts(field: &str, json: &Json) -> Result<FacetRangeCounts, String> {
+    let start = match json.find("start") {
+        Some(start_json) => try!(json_to_range_value(start_json, field)),
+        None => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): start not found for {}", field))
+    };
+    let end = match json.find("end") {
+        Some(end_json) => try!(json_to_range_value(end_json, field)),
+        None => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): end not found for {}", field))
+    };
+    let gap = match json.find("gap") {
+        Some(&Json::String(ref s)) => RangeGap::DateMath(s.clone()),
+        Some(&Json::F64(f)) => RangeGap::Float(f),
+        Some(gap_json) if gap_json.is_i64() || gap_json.is_u64() => match gap_json.as_i64() {
+            Some(i) => RangeGap::Int(i),
+            None => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): gap {} is out of range for {}", gap_json, field))
+        },
+        _ => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): gap not found for {}", field))
+    };
+    let mut buckets = Vec::new();
+    match json.find("counts") {
+        Some(&Json::Array(ref flat)) => {
+            for pair in flat.chunks(2) {
+                if pair.len() != 2 {
+                    return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): odd number of elements for {}", field));
+                }
+                let count = match pair[1].as_u64() {
+                    Some(c) => c,
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): count is not a number for {}", field))
+                };
+                let bucket_start = match pair[0].as_string() {
+                    Some(s) => try!(parse_range_value_like(s, &start, field)),
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): bucket is not a string for {}", field))
+                };
+                let bucket_end = bucket_upper_bound(&bucket_start, &gap, &end);
+                buckets.push(FacetRangeBucket{start: bucket_start, end: bucket_end, count: count});
+            }
+        },
+        _ => return Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): counts is not a JSON list for {}", field))
+    }
+    Ok(FacetRangeCounts{field: field.to_string(),
+        buckets: buckets,
+        start: start,
+        end: end,
+        gap: gap,
+        before: json.find("before").and_then(|x| x.as_u64()),
+        after: json.find("after").and_then(|x| x.as_u64()),
+        between: json.find("between").and_then(|x| x.as_u64())})
+}
+
+/// Parses interval facet object of {interval: count} pairs
+fn parse_interval_counts(field: &str, json: &Json) -> Result<FacetIntervalCounts, String> {
+    match json {
+        & Json::Object(ref tm) => {
+            let mut counts = Vec::with_capacity(tm.len());
+            for (key, count_json) in tm.iter() {
+                match count_json.as_u64() {
+                    Some(count) => counts.push(FacetIntervalCount{key: key.clone(), bounds: parse_interval(key), count: count}),
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (facet_intervals): count is not a number for {}", field))
+                }
+            }
+            counts.sort_by(|a, b| compare_intervals(&a.bounds, &b.bounds));
+            Ok(FacetIntervalCounts{field: field.to_string(), counts: counts})
+        },
+        _ => Err(format!("SolrQueryResponse JSON parsing error (facet_intervals): {} is not an object", field))
+    }
+}
+
+/// Parses list of pivot entries, recursing into nested pivots
+fn parse_pivot_entries(json: &Json) -> Result<Vec<PivotEntry>, String> {
+    match json {
+        & Json::Array(ref list) => {
+            let mut entries = Vec::with_capacity(list.len());
+            for entry_json in list.iter() {
+                let field = match entry_json.find("field").and_then(|x| x.as_string()) {
+                    Some(f) => f.to_string(),
+                    None => return Err("SolrQueryResponse JSON parsing error (facet_pivot): field not found".to_string())
+                };
+                let count = match entry_json.find("count").and_then(|x| x.as_u64()) {
+                    Some(c) => c,
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (facet_pivot): count not found for {}", field))
+                };
+                let value = match entry_json.find("value") {
+                    Some(&Json::String(ref v)) => Some(v.clone()),
+                    Some(&Json::Null) | None => None,
+                    Some(other) => Some(other.to_string())
+                };
+                let pivot = match entry_json.find("pivot") {
+                    Some(pivot_json) => try!(parse_pivot_entries(pivot_json)),
+                    None => Vec::new()
+                };
+                entries.push(PivotEntry{field: field, value: value, count: count, pivot: pivot});
+            }
+            Ok(entries)
+        },
+        _ => Err("SolrQueryResponse JSON parsing error (facet_pivot): pivot is not a JSON list".to_string())
+    }
+}
+
+fn json_to_range_value(json: &Json, field: &str) -> Result<RangeValue, String> {
+    match json {
+        & Json::I64(i) => Ok(RangeValue::Int(i)),
+        & Json::U64(u) if u <= i64::max_value() as u64 => Ok(RangeValue::Int(u as i64)),
+        & Json::F64(f) => Ok(RangeValue::Float(f)),
+        & Json::String(ref s) => Ok(RangeValue::Date(s.clone())),
+        _ => Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): unexpected bound type for {}", field))
+    }
+}
+
+/// Parses bucket value, which is always a string, as the same type as range start
+fn parse_range_value_like(value: &str, like: &RangeValue, field: &str) -> Result<RangeValue, String> {
+    let parsed = match *like {
+        RangeValue::Int(_) => value.parse::<i64>().ok().map(RangeValue::Int),
+        RangeValue::Float(_) => value.parse::<f64>().ok().map(RangeValue::Float),
+        RangeValue::Date(_) => Some(RangeValue::Date(value.to_string()))
+    };
+    match parsed {
+        Some(v) => Ok(v),
+        None => Err(format!("SolrQueryResponse JSON parsing error (facet_ranges): invalid bucket {} for {}", value, field))
+    }
+}
+
+fn bucket_upper_bound(start: &RangeValue, gap: &RangeGap, end: &RangeValue) -> Option<RangeValue> {
+    match (start, gap, end) {
+        (&RangeValue::Int(s), &RangeGap::Int(g), &RangeValue::Int(e)) => Some(RangeValue::Int(match s.checked_add(g) { Some(u) if u < e => u, _ => e })),
+        (&RangeValue::Float(s), &RangeGap::Float(g), &RangeValue::Float(e)) => Some(RangeValue::Float(if s + g < e { s + g } else { e })),
+        _ => None
+    }
+}
+
+/// Parses interval in Solr syntax, for example [0,10) or (*,2015-01-01T00:00:00Z]
+fn parse_interval(key: &str) -> Option<IntervalBounds> {
+    let include_start = match key.chars().next() {
+        Some('[') => true,
+        Some('(') => false,
+        _ => return None
+    };
+    let include_end = match key.chars().last() {
+        Some(']') => true,
+        Some(')') => false,
+        _ => return None
+    };
+    let inner = &key[1..key.len() - 1];
+    let mut parts = inner.splitn(2, ',');
+    match (parts.next(), parts.next()) {
+        (Some(start), Some(end)) => Some(IntervalBounds{start: parse_interval_value(start.trim()),
+            end: parse_interval_value(end.trim()),
+            include_start: include_start,
+            include_end: include_end}),
+        _ => None
+    }
+}
+
+fn parse_interval_value(value: &str) -> Option<RangeValue> {
+    if value == "*" {
+        None
+    } else if let Ok(i) = value.parse::<i64>() {
+        Some(RangeValue::Int(i))
+    } else if let Ok(f) = value.parse::<f64>() {
+        Some(RangeValue::Float(f))
+    } else {
+        Some(RangeValue::Date(value.to_string()))
+    }
+}
+
+/// Orders intervals by lower bound, unbounded first and keyed intervals last
+fn compare_intervals(a: &Option<IntervalBounds>, b: &Option<IntervalBounds>) -> Ordering {
+    match (a, b) {
+        (&Some(ref a), &Some(ref b)) => match (&a.start, &b.start) {
+            (&None, &None) => Ordering::Equal,
+            (&None, &Some(_)) => Ordering::Less,
+            (&Some(_), &None) => Ordering::Greater,
+            (&Some(ref x), &Some(ref y)) => compare_range_values(x, y)
+        },
+        (&Some(_), &None) => Ordering::Less,
+        (&None, &Some(_)) => Ordering::Greater,
+        (&None, &None) => Ordering::Equal
+    }
+}
+
+fn compare_range_values(a: &RangeValue, b: &RangeValue) -> Ordering {
+    match (a, b) {
+        (&RangeValue::Date(ref x), &RangeValue::Date(ref y)) => x.cmp(y),
+        (&RangeValue::Date(_), _) | (_, &RangeValue::Date(_)) => Ordering::Equal,
+        _ => as_f64(a).partial_cmp(&as_f64(b)).unwrap_or(Ordering::Equal)
+    }
+}
+
+fn as_f64(value: &RangeValue) -> f64 {
+    match *value {
+        RangeValue::Int(i) => i as f64,
+        RangeValue::Float(f) => f,
+        RangeValue::Date(_) => 0.0
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+use document::{SolrDocument, SolrValue};
+use response::query::SolrQueryResponse;
+
+/// List of documents with its total count, as returned for groups and expanded results
+#[derive(Debug)]
+pub struct SolrDocList {
+    /// Total number of documents found
+    pub total: u64,
+    /// Rows offset (zero based)
+    pub start: u64,
+    pub items: Vec<SolrDocument>
+}
+
+/// Single group of documents sharing the same value
+#[derive(Debug)]
+pub struct SolrGroup {
+    /// Group value, `SolrValue::Null` for documents without a value
+    pub group_value: SolrValue,
+    pub doclist: SolrDocList
+}
+
+/// Result of a single group command (group.field, group.query or group.func)
+#[derive(Debug)]
+pub struct SolrGroupCommand {
+    /// Field name, query or function of the command
+    pub name: String,
+    /// Number of documents matching the query
+    pub matches: u64,
+    /// Number of groups, when requested with group.ngroups
+    pub ngroups: Option<u64>,
+    /// Groups, empty for group.query and group.format=simple
+    pub groups: Vec<SolrGroup>,
+    /// Documents of group.query or group.format=simple commands
+    pub doclist: Option<SolrDocList>
+}
+
+/* Example JSON of grouped:
+```ignore
+"grouped": {
+  "city": {
+    "matches": 16,
+    "ngroups": 2,
+    "groups": [
+      {"groupValue": "London", "doclist": {"numFound": 12, "start": 0, "docs": [{"id": "1"}]}},
+      {"groupValue": null, "doclist": {"numFound": 4, "start": 0, "docs": [{"id": "7"}]}}
+    ]
+  },
+  "price:[0 TO 10]": {
+    "matches": 16,
+    "doclist": {"numFound": 3, "start": 0, "docs": [{"id": "2"}]}
+  }
+}
+```
+*/
+impl SolrDocList {
+    /// Deserializes SolrDocList from JSON with numFound, start and docs
+    pub fn from_json(json: &Json, section: &str) -> Result<SolrDocList, String> {
+        let total = match json.find("numFound").and_then(|x| x.as_u64()) {
+            Some(t) => t,
+            None => return Err(format!("SolrQueryResponse JSON parsing error ({}): numFound not found", section))
+        };
+        let start = match json.find("start").and_then(|x| x.as_u64()) {
+            Some(s) => s,
+            None => return Err(format!("SolrQueryResponse JSON parsing error ({}): start not found", section))
+        };
+        let mut items = Vec::new();
+        match json.find("docs") {
+            Some(&Json::Array(ref docs)) => {
+                for doc_json in docs.iter() {
+                    items.push(try!(SolrQueryResponse::parse_doc(doc_json)));
+                }
+            },
+            Some(_) => return Err(format!("SolrQueryResponse JSON parsing error ({}): docs is not a JSON list", section)),
+            None => return Err(format!("SolrQueryResponse JSON parsing error ({}): docs not found", section))
+        }
+        Ok(SolrDocList{total: total, start: start, items: items})
+    }
+}
+
+/// Deserializes group commands from grouped JSON, ordered by name
+pub fn parse_grouped(json: &Json) -> Result<Vec<SolrGroupCommand>, String> {
+    match json {
+        & Json::Object(ref tm) => {
+            let mut commands = Vec::with_capacity(tm.len());
+            for (name, command_json) in tm.iter() {
+                let matches = match command_json.find("matches").and_then(|x| x.as_u64()) {
+                    Some(m) => m,
+                    None => return Err(format!("SolrQueryResponse JSON parsing error (grouped): matches not found for {}", name))
+                };
+                let mut groups = Vec::new();
+                match command_json.find("groups") {
+                    Some(&Json::Array(ref list)) => {
+                        for group_json in list.iter() {
+                            let group_value = match group_json.find("groupValue") {
+                                Some(v) => try!(SolrQueryResponse::parse_value(v)),
+                                None => return Err(format!("SolrQueryResponse JSON parsing error (grouped): groupValue not found for {}", name))
+                            };
+                            let doclist = match group_json.find("doclist") {
+                                Some(d) => try!(SolrDocList::from_json(d, "grouped")),
+                                None => return Err(format!("SolrQueryResponse JSON parsing error (grouped): doclist not found for {}", name))
+                            };
+                            groups.push(SolrGroup{group_value: group_value, doclist: doclist});
+                        }
+                    },
+                    Some(_) => return Err(format!("SolrQueryResponse JSON parsing error (grouped): groups is not a JSON list for {}", name)),
+                    None => ()
+                }
+                let doclist = match command_json.find("doclist") {
+                    Some(d) => Some(try!(SolrDocList::from_json(d, "grouped"))),
+                    None => None
+                };
+                commands.push(SolrGroupCommand{name: name.clone(),
+                    matches: matches,
+                    ngroups: command_json.find("ngroups").and_then(|x| x.as_u64()),
+                    groups: groups,
+                    doclist: doclist});
+            }
+            Ok(commands)
+        },
+        _ => Err("SolrQueryResponse JSON parsing error: grouped is not an object".to_string())
+    }
+}
+
+/// Deserializes expanded JSON into document lists by collapse value
+pub fn parse_expanded(json: &Json) -> Result<BTreeMap<String, SolrDocList>, String> {
+    match json {
+        & Json::Object(ref tm) => {
+            let mut expanded = BTreeMap::new();
+            for (value, doclist_json) in tm.iter() {
+                expanded.insert(value.clone(), try!(SolrDocList::from_json(doclist_json, "expanded")));
+            }
+            Ok(expanded)
+        },
+        _ => Err("SolrQueryResponse JSON parsing error: expanded is not an object".to_string())
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+
+/// Highlighted snippets by field name
+pub type FieldHighlights = BTreeMap<String, Vec<String>>;
+
+/// Parsed highlighting section of the query response
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolrHighlighting {
+    /// Highlighted fields by document unique key
+    pub documents: BTreeMap<String, FieldHighlights>
+}
+
+/* Example JSON of highlighting:
+```ignore
+"highlighting": {
+  "1": {"title": ["How to train your <em>dragon</em>"]},
+  "2": {}
+}
+```
+*/
+impl SolrHighlighting {
+    /// Finds highlighted fields of a document by its unique key
+    pub fn get(&self, id: &str) -> Option<&FieldHighlights> {
+        self.documents.get(id)
+    }
+
+    /// Finds snippets of a document field
+    pub fn snippets(&self, id: &str, field: &str) -> Option<&Vec<String>> {
+        self.documents.get(id).and_then(|f| f.get(field))
+    }
+
+    /// Deserializes SolrHighlighting from highlighting JSON
+    pub fn from_json(json: &Json) -> Result<SolrHighlighting, String> {
+        let mut highlighting = SolrHighlighting{documents: BTreeMap::new()};
+        match json {
+            & Json::Object(ref docs) => {
+                for (id, fields_json) in docs.iter() {
+                    let mut fields = BTreeMap::new();
+                    match fields_json {
+                        & Json::Object(ref tm) => {
+                            for (field, snippets_json) in tm.iter() {
+                                let snippets = match snippets_json {
+                                    & Json::Array(ref list) => list.iter().filter_map(|x| x.as_string()).map(|x| x.to_string()).collect(),
+                                    & Json::String(ref s) => vec!(s.clone()),
+                                    _ => return Err(format!("SolrQueryResponse JSON parsing error (highlighting): snippets are not a JSON list for {}", id))
+                                };
+                                fields.insert(field.clone(), snippets);
+                            }
+                        },
+                        _ => return Err(format!("SolrQueryResponse JSON parsing error (highlighting): {} is not an object", id))
+                    }
+                    highlighting.documents.insert(id.clone(), fields);
+                }
+                Ok(highlighting)
+            },
+            _ => Err("SolrQueryResponse JSON parsing error: highlighting is not an object".to_string())
+        }
+    }
+}
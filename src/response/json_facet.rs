@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+
+/// Typed bucket value or aggregation result of the JSON Facet API
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonFacetValue {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    /// For example multiple percentiles
+    List(Vec<JsonFacetValue>)
+}
+
+impl JsonFacetValue {
+    /// Returns numeric value as f64, None for non-numeric values
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            JsonFacetValue::Int(i) => Some(i as f64),
+            JsonFacetValue::Float(f) => Some(f),
+            _ => None
+        }
+    }
+}
+
+/// Bucket of the JSON Facet API result tree.
+/// The root of the tree, returned in the facets section, is a bucket too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonFacetBucket {
+    /// Bucket value, `None` for the root, query facets and special buckets
+    pub val: Option<JsonFacetValue>,
+    pub count: u64,
+    /// Aggregation results by name
+    pub metrics: BTreeMap<String, JsonFacetValue>,
+    /// Sub-facet results by name
+    pub facets: BTreeMap<String, JsonFacetResult>
+}
+
+/// Result of a single named facet
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonFacetResult {
+    /// Result of terms and range facets
+    Buckets(JsonFacetBuckets),
+    /// Result of query facets
+    Bucket(JsonFacetBucket),
+    Heatmap(HeatmapResult)
+}
+
+/// Buckets of terms and range facets with special buckets, when requested
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonFacetBuckets {
+    pub buckets: Vec<JsonFacetBucket>,
+    pub num_buckets: Option<u64>,
+    pub missing: Option<Box<JsonFacetBucket>>,
+    pub all_buckets: Option<Box<JsonFacetBucket>>,
+    pub before: Option<Box<JsonFacetBucket>>,
+    pub after: Option<Box<JsonFacetBucket>>,
+    pub between: Option<Box<JsonFacetBucket>>
+}
+
+/// Result of heatmap facet
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapResult {
+    pub grid_level: u64,
+    pub columns: u64,
+    pub rows: u64,
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_y: f64,
+    pub max_y: f64,
+    /// Counts by row and column for ints2D format, rows without documents are empty
+    pub counts: Option<Vec<Vec<u64>>>,
+    /// Base64 encoded image for png format
+    pub counts_png: Option<String>
+}
+
+/* Example JSON of facets:
+```ignore
+"facets": {
+  "count": 32,
+  "avg_price": 12.5,
+  "categories": {
+    "numBuckets": 2,
+    "buckets": [
+      {"val": "books", "count": 20, "avg_price": 10.0},
+      {"val": "music", "count": 12, "avg_price": 16.7}
+    ]
+  },
+  "cheap": {"count": 7}
+}
+```
+*/
+impl JsonFacetBucket {
+    /// Finds aggregation result by name
+    pub fn metric(&self, name: &str) -> Option<&JsonFacetValue> {
+        self.metrics.get(name)
+    }
+
+    /// Finds sub-facet result by name
+    pub fn facet(&self, name: &str) -> Option<&JsonFacetResult> {
+        self.facets.get(name)
+    }
+
+    /// Deserializes JsonFacetBucket, including nested facets, from JSON object
+    pub fn from_json(json: &Json) -> Result<JsonFacetBucket, String> {
+        match json {
+            & Json::Object(ref tm) => {
+                let mut bucket = JsonFacetBucket{val: None, count: 0, metrics: BTreeMap::new(), facets: BTreeMap::new()};
+                for (k, v) in tm.iter() {
+                    match k.as_ref() {
+                        "val" => bucket.val = Some(try!(parse_value(v))),
+                        "count" => match v.as_u64() {
+                            Some(count) => bucket.count = count,
+                            None => return Err("SolrQueryResponse JSON parsing error (facets): count is not a number".to_string())
+                        },
+                        _ => match v {
+                            & Json::Object(_) => { bucket.facets.insert(k.clone(), try!(parse_result(v))); },
+                            & Json::Null => (),
+                            _ => { bucket.metrics.insert(k.clone(), try!(parse_value(v))); }
+                        }
+                    }
+                }
+                Ok(bucket)
+            },
+            _ => Err("SolrQueryResponse JSON parsing error (facets): bucket is not an object".to_string())
+        }
+    }
+}
+
+fn parse_result(json: &Json) -> Result<JsonFacetResult, String> {
+    if json.find("buckets").is_some() {
+        parse_buckets(json).map(JsonFacetResult::Buckets)
+    } else if json.find("gridLevel").is_some() {
+        parse_heatmap(json).map(JsonFacetResult::Heatmap)
+    } else {
+        JsonFacetBucket::from_json(json).map(JsonFacetResult::Bucket)
+    }
+}
+
+fn parse_buckets(json: &Json) -> Result<JsonFacetBuckets, String> {
+    let mut buckets = Vec::new();
+    match json.find("buckets") {
+        Some(&Json::Array(ref list)) => {
+            for bucket_json in list.iter() {
+                buckets.push(try!(JsonFacetBucket::from_json(bucket_json)));
+            }
+        },
+        _ => return Err("SolrQueryResponse JSON parsing error (facets): buckets is not a JSON list".to_string())
+    }
+    Ok(JsonFacetBuckets{buckets: buckets,
+        num_buckets: json.find("numBuckets").and_then(|x| x.as_u64()),
+        missing: try!(parse_special_bucket(json, "missing")),
+        all_buckets: try!(parse_special_bucket(json, "allBuckets")),
+        before: try!(parse_special_bucket(json, "before")),
+        after: try!(parse_special_bucket(json, "after")),
+        between: try!(parse_special_bucket(json, "between"))})
+}
+
+fn parse_special_bucket(json: &Json, name: &str) -> Result<Option<Box<JsonFacetBucket>>, String> {
+    match json.find(name) {
+        Some(bucket_json) => Ok(Some(Box::new(try!(JsonFacetBucket::from_json(bucket_json))))),
+        None => Ok(None)
+    }
+}
+
+fn parse_heatmap(json: &Json) -> Result<HeatmapResult, String> {
+    let counts = match json.find("counts_ints2D") {
+        Some(&Json::Array(ref rows)) => {
+            let mut counts = Vec::with_capacity(rows.len());
+            for row in rows.iter() {
+                match *row {
+                    Json::Array(ref cells) => counts.push(cells.iter().map(|x| x.as_u64().unwrap_or(0)).collect()),
+                    _ => counts.push(Vec::new())
+                }
+            }
+            Some(counts)
+        },
+        _ => None
+    };
+    Ok(HeatmapResult{grid_level: json.find("gridLevel").and_then(|x| x.as_u64()).unwrap_or(0),
+        columns: json.find("columns").and_then(|x| x.as_u64()).unwrap_or(0),
+        rows: json.find("rows").and_then(|x| x.as_u64()).unwrap_or(0),
+        min_x: json.find("minX").and_then(|x| x.as_f64()).unwrap_or(0.0),
+        max_x: json.find("maxX").and_then(|x| x.as_f64()).unwrap_or(0.0),
+        min_y: json.find("minY").and_then(|x| x.as_f64()).unwrap_or(0.0),
+        max_y: json.find("maxY").and_then(|x| x.as_f64()).unwrap_or(0.0),
+        counts: counts,
+        counts_png: json.find("counts_png").and_then(|x| x.as_string()).map(|x| x.to_string())})
+}
+
+fn parse_value(json: &Json) -> Result<JsonFacetValue, String> {
+    match json {
+        & Json::I64(i) => Ok(JsonFacetValue::Int(i)),
+        & Json::U64(u) => Ok(JsonFacetValue::Int(u as i64)),
+        & Json::F64(f) => Ok(JsonFacetValue::Float(f)),
+        & Json::String(ref s) => Ok(JsonFacetValue::String(s.clone())),
+        & Json::Boolean(b) => Ok(JsonFacetValue::Boolean(b)),
+        & Json::Array(ref list) => {
+            let mut values = Vec::with_capacity(list.len());
+            for v in list.iter() {
+                values.push(try!(parse_value(v)));
+            }
+            Ok(JsonFacetValue::List(values))
+        },
+        _ => Err("SolrQueryResponse JSON parsing error (facets): unexpected value type".to_string())
+    }
+}
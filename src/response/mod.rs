@@ -1,16 +1,36 @@
 pub use self::update::{SolrUpdateResponse, SolrUpdateResult};
 pub use self::query::{SolrQueryResponse, SolrQueryResult};
 pub use self::ping::{SolrPingResponse, SolrPingResult};
+pub use self::terms::{SolrTermsResponse, SolrTermsResult, TermFrequency};
+pub use self::suggest::{SolrSuggestResponse, SolrSuggestResult, SuggestResult, Suggestion};
+pub use self::facet::{SolrFacetCounts, FacetFieldCounts, FacetCount};
+pub use self::facet::{FacetRangeCounts, FacetRangeBucket, FacetIntervalCounts, FacetIntervalCount};
+pub use self::facet::{RangeValue, RangeGap, IntervalBounds, FacetPivotCounts, PivotEntry};
+pub use self::group::{SolrDocList, SolrGroup, SolrGroupCommand};
+pub use self::highlight::{SolrHighlighting, FieldHighlights};
+pub use self::stats::{SolrStats, FieldStats};
+pub use self::spellcheck::{SolrSpellcheck, SpellcheckSuggestion, SpellcheckAlternative, SpellcheckCollation};
+pub use self::json_facet::{JsonFacetBucket, JsonFacetResult, JsonFacetBuckets, JsonFacetValue, HeatmapResult};
 
 mod update;
 mod query;
 mod ping;
+mod suggest;
+mod terms;
+mod facet;
+mod json_facet;
+mod highlight;
+mod spellcheck;
+mod group;
+mod more_like_this;
+mod stats;
 
 use rustc_serialize::{json, Decodable, Decoder};
 use rustc_serialize::json::Json;
 
 
 /// SolrError
+#[derive(Clone, Debug)]
 pub struct SolrError {
     /// HTTP status.
     /// When failed to connect, it will be 0 (zero).
@@ -18,18 +38,75 @@ pub struct SolrError {
     /// Time it took to execute the request in milliseconds
     pub time: i32,
     /// Detailed error message
-    pub message: String
+    pub message: String,
+    pub kind: SolrErrorKind
+}
+
+/// Kind of SolrError
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolrErrorKind {
+    /// Network, parsing or Solr error, described by the message
+    Other,
+    /// Document _version_ didn't match the expected one (HTTP 409 Conflict)
+    VersionConflict(VersionConflict)
+}
+
+/// Details of optimistic concurrency failure
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionConflict {
+    /// Unique key of the conflicting document
+    pub id: String,
+    /// Expected version sent with the document, `None` when Solr doesn't report it
+    pub expected: Option<i64>,
+    /// Actual version of the indexed document, `None` when the document doesn't exist
+    pub actual: Option<i64>
+}
+
+impl VersionConflict {
+    /// Parses Solr conflict message, for example
+    /// `version conflict for 1 expected=1628 actual=1631` or `Document not found for update.  id=1`
+    pub fn parse(message: &str) -> Option<VersionConflict> {
+        if let Some(start) = message.find("version conflict for ") {
+            let rest = &message[start + "version conflict for ".len()..];
+            let end = match rest.rfind(" expected=") {
+                Some(end) => end,
+                None => return None
+            };
+            let numbers = &rest[end..];
+            Some(VersionConflict{id: rest[..end].to_string(),
+                                 expected: parse_number(numbers, "expected="),
+                                 actual: parse_number(numbers, "actual=").and_then(|v| if v > 0 { Some(v) } else { None })})
+        } else if message.starts_with("Document not found for update.") {
+            message.find("id=").map(|start| VersionConflict{id: message[start + 3..].trim().to_string(), expected: Some(1), actual: None})
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_number(value: &str, prefix: &str) -> Option<i64> {
+    value.find(prefix).and_then(|start| {
+        let number: String = value[start + prefix.len()..].chars().take_while(|c| *c == '-' || c.is_digit(10)).collect();
+        number.parse().ok()
+    })
 }
 
 impl Decodable for SolrError {
     fn decode<D: Decoder>(d: &mut D) -> Result<SolrError, D::Error> {
         d.read_struct("root", 0, |d| {
             d.read_struct_field("error", 0, |d| {
+                let message: String = try!(d.read_struct_field("msg", 0, Decodable::decode));
+                let status: i32 = try!(d.read_struct_field("code", 1, Decodable::decode));
+                let kind = match VersionConflict::parse(&message) {
+                    Some(conflict) if status == 409 => SolrErrorKind::VersionConflict(conflict),
+                    _ => SolrErrorKind::Other
+                };
                 Ok(SolrError{
-                    message: try!(d.read_struct_field("msg", 0, Decodable::decode)),
-                    status: try!(d.read_struct_field("code", 1, Decodable::decode)),
+                    message: message,
+                    status: status,
                     // TODO: implement time parsing from request header
-                    time: 0})
+                    time: 0,
+                    kind: kind})
             })
         })
     }
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+use response::group::SolrDocList;
+
+/* Example JSON of moreLikeThis:
+```ignore
+"moreLikeThis": {
+  "1": {"numFound": 3, "start": 0, "docs": [{"id": "4"}, {"id": "7"}]},
+  "2": {"numFound": 0, "start": 0, "docs": []}
+}
+```
+Solr before 4.0 returns the same as flat list of unique key and document list pairs.
+*/
+/// Deserializes moreLikeThis JSON into similar documents by unique key of the source document
+pub fn parse_more_like_this(json: &Json) -> Result<BTreeMap<String, SolrDocList>, String> {
+    let mut similar = BTreeMap::new();
+    match json {
+        & Json::Object(ref tm) => {
+            for (id, doclist_json) in tm.iter() {
+                similar.insert(id.clone(), try!(SolrDocList::from_json(doclist_json, "moreLikeThis")));
+            }
+        },
+        & Json::Array(ref list) => {
+            for pair in list.chunks(2) {
+                match (pair[0].as_string(), pair.get(1)) {
+                    (Some(id), Some(doclist_json)) => {
+                        similar.insert(id.to_string(), try!(SolrDocList::from_json(doclist_json, "moreLikeThis")));
+                    },
+                    _ => return Err("SolrQueryResponse JSON parsing error (moreLikeThis): list is not flat id/docs pairs".to_string())
+                }
+            }
+        },
+        _ => return Err("SolrQueryResponse JSON parsing error: moreLikeThis is not an object".to_string())
+    }
+    Ok(similar)
+}
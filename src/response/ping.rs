@@ -1,6 +1,6 @@
 use rustc_serialize::{json, Decodable, Decoder};
 use rustc_serialize::json::Json;
-use response::SolrError;
+use response::{SolrError, SolrErrorKind};
 
 pub type SolrPingResult = Result<SolrPingResponse, SolrError>;
 
@@ -51,7 +51,7 @@ impl SolrPingResponse {
         if error.len() == 0 {
             Ok(response)
         } else {
-            Err(SolrError{time: 0, status: 0, message: error})
+            Err(SolrError{time: 0, status: 0, message: error, kind: SolrErrorKind::Other})
         }
     }
 }
@@ -1,7 +1,16 @@
 use rustc_serialize::{json, Decodable, Decoder};
 use rustc_serialize::json::Json;
 use document::{SolrDocument, SolrField, SolrValue};
-use response::SolrError;
+use response::{SolrError, SolrErrorKind};
+use response::facet::SolrFacetCounts;
+use response::json_facet::JsonFacetBucket;
+use response::highlight::{SolrHighlighting, FieldHighlights};
+use response::spellcheck::SolrSpellcheck;
+use response::group::{self, SolrGroupCommand, SolrDocList};
+use response::more_like_this;
+use response::stats::SolrStats;
+use mapping::FromSolrDocument;
+use std::collections::BTreeMap;
 
 pub type SolrQueryResult = Result<SolrQueryResponse, SolrError>;
 
@@ -19,7 +28,27 @@ pub struct SolrQueryResponse {
     /// Rows offset (zero based)
     pub start: u64,
     /// Current page of found Solr documents
-    pub items: Vec<SolrDocument>
+    pub items: Vec<SolrDocument>,
+    /// Facet counts, present only when faceting was requested
+    pub facet_counts: Option<SolrFacetCounts>,
+    /// Root bucket of the JSON Facet API results, present only when json.facet was requested
+    pub json_facets: Option<JsonFacetBucket>,
+    /// Highlighted snippets, present only when highlighting was requested
+    pub highlighting: Option<SolrHighlighting>,
+    /// Group commands ordered by name, present only when grouping was requested
+    pub grouped: Option<Vec<SolrGroupCommand>>,
+    /// Collapsed documents by collapse field value, present only when expand was requested
+    pub expanded: Option<BTreeMap<String, SolrDocList>>,
+    /// Spellcheck suggestions and collations, present only when spellcheck was requested
+    pub spellcheck: Option<SolrSpellcheck>,
+    /// Field statistics, present only when stats were requested
+    pub stats: Option<SolrStats>,
+    /// Similar documents by unique key of each result, present only when mlt was requested
+    pub more_like_this: Option<BTreeMap<String, SolrDocList>>,
+    /// Seed document of the /mlt handler, present only when mlt.match.include was requested
+    pub mlt_match: Option<SolrDocList>,
+    /// Cursor mark of the next page, present only when cursorMark was requested
+    pub next_cursor_mark: Option<String>
 }
 
 /* Example JSON of query response: 
@@ -48,7 +77,7 @@ pub struct SolrQueryResponse {
 impl SolrQueryResponse {
     /// Deserializes SolrQueryResponse from JSON string
     pub fn from_json_str(json_str: &str) -> SolrQueryResult {
-        let mut response = SolrQueryResponse{status: 0, time: 0, total: 0, start: 0, items: Vec::new() };
+        let mut response = SolrQueryResponse{status: 0, time: 0, total: 0, start: 0, items: Vec::new(), facet_counts: None, json_facets: None, highlighting: None, grouped: None, expanded: None, spellcheck: None, stats: None, more_like_this: None, mlt_match: None, next_cursor_mark: None };
         let mut error: String = "".to_string();
         match Json::from_str(json_str) {
             Ok(json) => match json {
@@ -99,7 +128,96 @@ impl SolrQueryResponse {
                                 None => error = "SolrQueryResponse JSON parsing error (response): docs not found".to_string()
                             }
                         },
-                        None => error = "SolrQueryResponse JSON parsing error: response not found".to_string()
+                        // grouped responses don't have response section, unless group.main is used
+                        None => if !tree_map.contains_key("grouped") {
+                            error = "SolrQueryResponse JSON parsing error: response not found".to_string()
+                        }
+                    }
+                    match tree_map.get(&"nextCursorMark".to_string()) {
+                        Some(&Json::String(ref next)) => response.next_cursor_mark = Some(next.clone()),
+                        Some(_) => error = "SolrQueryResponse JSON parsing error: nextCursorMark is not a string".to_string(),
+                        None => ()
+                    }
+                    match tree_map.get(&"grouped".to_string()) {
+                        Some(g) => {
+                            match group::parse_grouped(g) {
+                                Ok(grouped) => response.grouped = Some(grouped),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"expanded".to_string()) {
+                        Some(ex) => {
+                            match group::parse_expanded(ex) {
+                                Ok(expanded) => response.expanded = Some(expanded),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"facet_counts".to_string()) {
+                        Some(fc) => {
+                            match SolrFacetCounts::from_json(fc) {
+                                Ok(facet_counts) => response.facet_counts = Some(facet_counts),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"highlighting".to_string()) {
+                        Some(hl) => {
+                            match SolrHighlighting::from_json(hl) {
+                                Ok(highlighting) => response.highlighting = Some(highlighting),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"spellcheck".to_string()) {
+                        Some(sc) => {
+                            match SolrSpellcheck::from_json(sc) {
+                                Ok(spellcheck) => response.spellcheck = Some(spellcheck),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"stats".to_string()) {
+                        Some(st) => {
+                            match SolrStats::from_json(st) {
+                                Ok(stats) => response.stats = Some(stats),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"moreLikeThis".to_string()) {
+                        Some(mlt) => {
+                            match more_like_this::parse_more_like_this(mlt) {
+                                Ok(similar) => response.more_like_this = Some(similar),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"match".to_string()) {
+                        Some(m) => {
+                            match SolrDocList::from_json(m, "match") {
+                                Ok(mlt_match) => response.mlt_match = Some(mlt_match),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
+                    }
+                    match tree_map.get(&"facets".to_string()) {
+                        Some(f) => {
+                            match JsonFacetBucket::from_json(f) {
+                                Ok(json_facets) => response.json_facets = Some(json_facets),
+                                Err(e) => error = e
+                            }
+                        },
+                        None => ()
                     }
                },
                _ => error = "SolrQueryResponse JSON parsing error: query response is not a JSON object.".to_string()
@@ -110,29 +228,98 @@ impl SolrQueryResponse {
         if error.len() == 0 {
             Ok(response)
         } else {
-            Err(SolrError{time: 0, status: 0, message: error})
+            Err(SolrError{time: 0, status: 0, message: error, kind: SolrErrorKind::Other})
+        }
+    }
+
+    /// Joins returned documents with their highlighted snippets,
+    /// using `unique_key` field (usually id) of the documents
+    pub fn highlighted_items(&self, unique_key: &str) -> Vec<(&SolrDocument, Option<&FieldHighlights>)> {
+        self.items.iter().map(|doc| {
+            let highlights = match (doc.get(unique_key), &self.highlighting) {
+                (Some(id), &Some(ref hl)) => match *id {
+                    SolrValue::String(ref s) => hl.get(s),
+                    SolrValue::I64(i) => hl.get(&i.to_string()),
+                    SolrValue::U64(u) => hl.get(&u.to_string()),
+                    _ => None
+                },
+                _ => None
+            };
+            (doc, highlights)
+        }).collect()
+    }
+
+    /// Converts returned documents into typed items
+    pub fn items_as<T: FromSolrDocument>(&self) -> Result<Vec<T>, SolrError> {
+        let mut items = Vec::with_capacity(self.items.len());
+        for doc in self.items.iter() {
+            items.push(try!(T::from_solr_document(doc)));
+        }
+        Ok(items)
+    }
+
+    /// Finds group command by field name, query or function
+    pub fn group(&self, name: &str) -> Option<&SolrGroupCommand> {
+        match self.grouped {
+            Some(ref g) => g.iter().find(|c| c.name == name),
+            None => None
         }
     }
 
-    fn parse_doc(doc_json: &Json) -> Result<SolrDocument, String> {
+    /// Deserializes a single document from JSON object
+    pub fn parse_doc(doc_json: &Json) -> Result<SolrDocument, String> {
         match doc_json {
             & Json::Object(ref tm) => {
-                let mut doc = SolrDocument{fields: Vec::with_capacity(tm.len())};
+                let mut doc = SolrDocument{fields: Vec::with_capacity(tm.len()), children: Vec::new()};
                 for (k, json_v) in tm.iter() {
-                    let v = match json_v {
-                        & Json::I64(i64) => SolrValue::I64(i64),
-                        & Json::U64(u64) => SolrValue::U64(u64),
-                        & Json::F64(f64) => SolrValue::F64(f64),
-                        & Json::String(ref string) => SolrValue::String(string.clone()),
-                        & Json::Boolean(bool) => SolrValue::Boolean(bool),
-                        _ => SolrValue::Null
-                    };
-                    doc.fields.push(SolrField{name: k.clone(), value: v});
+                    match (&k[..], json_v) {
+                        ("_childDocuments_", &Json::Array(ref children)) => {
+                            for child_json in children.iter() {
+                                doc.children.push(try!(SolrQueryResponse::parse_doc(child_json)));
+                            }
+                        },
+                        // labelled nested child documents, a single child is returned as an object
+                        (_, &Json::Object(_)) => {
+                            let child = try!(SolrQueryResponse::parse_doc(json_v));
+                            doc.fields.push(SolrField{name: k.clone(), value: SolrValue::Documents(vec!(child))});
+                        },
+                        (_, &Json::Array(ref list)) if !list.is_empty() && list.iter().all(|x| x.is_object()) => {
+                            let mut children = Vec::with_capacity(list.len());
+                            for child_json in list.iter() {
+                                children.push(try!(SolrQueryResponse::parse_doc(child_json)));
+                            }
+                            doc.fields.push(SolrField{name: k.clone(), value: SolrValue::Documents(children)});
+                        },
+                        _ => {
+                            let v = try!(SolrQueryResponse::parse_value(json_v));
+                            doc.fields.push(SolrField{name: k.clone(), value: v});
+                        }
+                    }
                 }
                 Ok(doc)
             },
             _ => Err("SolrQueryResponse JSON parsing error (response => docs): doc is not an object".to_string())
         }
     }
+
+    /// Deserializes a single field value from JSON
+    pub fn parse_value(json_v: &Json) -> Result<SolrValue, String> {
+        let v = match json_v {
+            & Json::I64(i64) => SolrValue::I64(i64),
+            & Json::U64(u64) => SolrValue::U64(u64),
+            & Json::F64(f64) => SolrValue::F64(f64),
+            & Json::String(ref string) => SolrValue::String(string.clone()),
+            & Json::Boolean(bool) => SolrValue::Boolean(bool),
+            & Json::Array(ref list) => {
+                let mut values = Vec::with_capacity(list.len());
+                for v in list.iter() {
+                    values.push(try!(SolrQueryResponse::parse_value(v)));
+                }
+                SolrValue::Array(values)
+            },
+            _ => SolrValue::Null
+        };
+        Ok(v)
+    }
 }
 
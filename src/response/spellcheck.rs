@@ -0,0 +1,156 @@
+use rustc_serialize::json::Json;
+
+/// Suggested correction of a misspelled term
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellcheckAlternative {
+    pub word: String,
+    /// Document frequency of the word, present only with spellcheck.extendedResults
+    pub freq: Option<u64>
+}
+
+/// Suggestions for a single misspelled term of the query
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellcheckSuggestion {
+    /// Original term of the query
+    pub token: String,
+    pub num_found: u64,
+    pub start_offset: u64,
+    pub end_offset: u64,
+    /// Document frequency of the original term, present only with spellcheck.extendedResults
+    pub orig_freq: Option<u64>,
+    pub alternatives: Vec<SpellcheckAlternative>
+}
+
+/// Corrected query built from suggestions
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellcheckCollation {
+    pub query: String,
+    /// Number of documents matching the query, present only with spellcheck.collateExtendedResults
+    pub hits: Option<u64>,
+    /// Pairs of original and corrected terms, present only with spellcheck.collateExtendedResults
+    pub corrections: Vec<(String, String)>
+}
+
+/// Parsed spellcheck section of the query response
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolrSpellcheck {
+    pub suggestions: Vec<SpellcheckSuggestion>,
+    pub collations: Vec<SpellcheckCollation>,
+    /// Whether all terms were found in the index, present only with spellcheck.extendedResults
+    pub correctly_spelled: Option<bool>
+}
+
+/* Example JSON of spellcheck:
+```ignore
+"spellcheck": {
+  "suggestions": [
+    "dragn", {"numFound": 1, "startOffset": 0, "endOffset": 5, "origFreq": 0,
+              "suggestion": [{"word": "dragon", "freq": 12}]},
+    "ridr", {"numFound": 1, "startOffset": 6, "endOffset": 10, "suggestion": ["rider"]}
+  ],
+  "correctlySpelled": false,
+  "collations": [
+    "collation", {"collationQuery": "dragon rider", "hits": 3,
+                  "misspellingsAndCorrections": ["dragn", "dragon", "ridr", "rider"]},
+    "collation", "dragon ride"
+  ]
+}
+```
+*/
+impl SolrSpellcheck {
+    /// Finds suggestions for a term of the query
+    pub fn suggestion(&self, token: &str) -> Option<&SpellcheckSuggestion> {
+        self.suggestions.iter().find(|x| x.token == token)
+    }
+
+    /// Returns the first collation, suitable for a "did you mean" line
+    pub fn best_collation(&self) -> Option<&str> {
+        self.collations.first().map(|x| &x.query[..])
+    }
+
+    /// Deserializes SolrSpellcheck from spellcheck JSON
+    pub fn from_json(json: &Json) -> Result<SolrSpellcheck, String> {
+        let mut spellcheck = SolrSpellcheck{suggestions: Vec::new(), collations: Vec::new(),
+            correctly_spelled: json.find("correctlySpelled").and_then(|x| x.as_boolean())};
+        match json.find("suggestions") {
+            Some(&Json::Array(ref list)) => {
+                for pair in list.chunks(2) {
+                    if pair.len() != 2 {
+                        return Err("SolrQueryResponse JSON parsing error (spellcheck): suggestions list is not flat name/value pairs".to_string());
+                    }
+                    match pair[0].as_string() {
+                        // Solr before 5.0 puts these in the suggestions list
+                        Some("correctlySpelled") => spellcheck.correctly_spelled = pair[1].as_boolean(),
+                        Some("collation") => spellcheck.collations.push(try!(parse_collation(&pair[1]))),
+                        Some(token) => spellcheck.suggestions.push(try!(parse_suggestion(token, &pair[1]))),
+                        None => return Err("SolrQueryResponse JSON parsing error (spellcheck): suggestion token is not a string".to_string())
+                    }
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (spellcheck): suggestions is not a JSON list".to_string()),
+            None => ()
+        }
+        match json.find("collations") {
+            Some(&Json::Array(ref list)) => {
+                for pair in list.chunks(2) {
+                    if pair.len() != 2 {
+                        return Err("SolrQueryResponse JSON parsing error (spellcheck): collations list is not flat name/value pairs".to_string());
+                    }
+                    spellcheck.collations.push(try!(parse_collation(&pair[1])));
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (spellcheck): collations is not a JSON list".to_string()),
+            None => ()
+        }
+        Ok(spellcheck)
+    }
+}
+
+fn parse_suggestion(token: &str, json: &Json) -> Result<SpellcheckSuggestion, String> {
+    let mut alternatives = Vec::new();
+    match json.find("suggestion") {
+        Some(&Json::Array(ref list)) => {
+            for alternative in list.iter() {
+                match *alternative {
+                    Json::String(ref word) => alternatives.push(SpellcheckAlternative{word: word.clone(), freq: None}),
+                    Json::Object(_) => match alternative.find("word").and_then(|x| x.as_string()) {
+                        Some(word) => alternatives.push(SpellcheckAlternative{word: word.to_string(),
+                            freq: alternative.find("freq").and_then(|x| x.as_u64())}),
+                        None => return Err(format!("SolrQueryResponse JSON parsing error (spellcheck): word not found for {}", token))
+                    },
+                    _ => return Err(format!("SolrQueryResponse JSON parsing error (spellcheck): invalid suggestion for {}", token))
+                }
+            }
+        },
+        _ => return Err(format!("SolrQueryResponse JSON parsing error (spellcheck): suggestion is not a JSON list for {}", token))
+    }
+    Ok(SpellcheckSuggestion{token: token.to_string(),
+        num_found: json.find("numFound").and_then(|x| x.as_u64()).unwrap_or(alternatives.len() as u64),
+        start_offset: json.find("startOffset").and_then(|x| x.as_u64()).unwrap_or(0),
+        end_offset: json.find("endOffset").and_then(|x| x.as_u64()).unwrap_or(0),
+        orig_freq: json.find("origFreq").and_then(|x| x.as_u64()),
+        alternatives: alternatives})
+}
+
+fn parse_collation(json: &Json) -> Result<SpellcheckCollation, String> {
+    match *json {
+        Json::String(ref query) => Ok(SpellcheckCollation{query: query.clone(), hits: None, corrections: Vec::new()}),
+        Json::Object(_) => {
+            let query = match json.find("collationQuery").and_then(|x| x.as_string()) {
+                Some(q) => q.to_string(),
+                None => return Err("SolrQueryResponse JSON parsing error (spellcheck): collationQuery not found".to_string())
+            };
+            let mut corrections = Vec::new();
+            if let Some(&Json::Array(ref list)) = json.find("misspellingsAndCorrections") {
+                for pair in list.chunks(2) {
+                    match (pair[0].as_string(), pair.get(1).and_then(|x| x.as_string())) {
+                        (Some(original), Some(correction)) => corrections.push((original.to_string(), correction.to_string())),
+                        _ => return Err("SolrQueryResponse JSON parsing error (spellcheck): invalid misspellingsAndCorrections".to_string())
+                    }
+                }
+            }
+            Ok(SpellcheckCollation{query: query, hits: json.find("hits").and_then(|x| x.as_u64()), corrections: corrections})
+        },
+        _ => Err("SolrQueryResponse JSON parsing error (spellcheck): collation is neither string nor object".to_string())
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::cmp::Ordering;
+use rustc_serialize::json::Json;
+use document::SolrValue;
+use response::query::SolrQueryResponse;
+
+/// Statistics of a single field, only the requested statistics are present
+#[derive(Debug, PartialEq)]
+pub struct FieldStats {
+    /// Minimum value, a string for string and date fields
+    pub min: Option<SolrValue>,
+    /// Maximum value, a string for string and date fields
+    pub max: Option<SolrValue>,
+    pub sum: Option<f64>,
+    pub count: Option<u64>,
+    pub missing: Option<u64>,
+    /// Mean value of numeric fields
+    pub mean: Option<f64>,
+    pub stddev: Option<f64>,
+    pub sum_of_squares: Option<f64>,
+    /// Pairs of percentile and value, in the requested order,
+    /// or ascending by percentile when Solr returns them as an object (json.nl=map)
+    pub percentiles: Vec<(f64, f64)>,
+    pub cardinality: Option<u64>,
+    pub count_distinct: Option<u64>,
+    pub distinct_values: Vec<SolrValue>,
+    /// Statistics by facet field, then by its value (stats.facet)
+    pub facets: BTreeMap<String, BTreeMap<String, FieldStats>>
+}
+
+/// Parsed stats section of the query response
+#[derive(Debug, PartialEq)]
+pub struct SolrStats {
+    /// Statistics by field name or the key set for the field
+    pub fields: BTreeMap<String, FieldStats>
+}
+
+/* Example JSON of stats:
+```ignore
+"stats": {
+  "stats_fields": {
+    "price": {
+      "min": 0.0, "max": 399.0, "count": 14, "missing": 2, "sum": 1143.0,
+      "sumOfSquares": 302456.0, "mean": 81.64, "stddev": 128.23,
+      "percentiles": ["50.0", 11.5, "99.0", 399.0],
+      "cardinality": 12,
+      "facets": {"inStock": {"true": {"min": 0.0, "max": 399.0, "count": 10}}}
+    },
+    "weight": null
+  }
+}
+```
+*/
+impl SolrStats {
+    /// Finds statistics of a field
+    pub fn field(&self, name: &str) -> Option<&FieldStats> {
+        self.fields.get(name)
+    }
+
+    /// Deserializes SolrStats from stats JSON
+    pub fn from_json(json: &Json) -> Result<SolrStats, String> {
+        let mut fields = BTreeMap::new();
+        match json.find("stats_fields") {
+            Some(&Json::Object(ref tm)) => {
+                for (name, field_json) in tm.iter() {
+                    fields.insert(name.clone(), try!(FieldStats::from_json(name, field_json)));
+                }
+            },
+            Some(_) => return Err("SolrQueryResponse JSON parsing error (stats): stats_fields is not an object".to_string()),
+            None => return Err("SolrQueryResponse JSON parsing error (stats): stats_fields not found".to_string())
+        }
+        Ok(SolrStats{fields: fields})
+    }
+}
+
+impl FieldStats {
+    /// Deserializes FieldStats from JSON, `null` meaning that no document has a value
+    pub fn from_json(name: &str, json: &Json) -> Result<FieldStats, String> {
+        let mut stats = FieldStats{min: None, max: None, sum: None, count: None, missing: None, mean: None,
+            stddev: None, sum_of_squares: None, percentiles: Vec::new(), cardinality: None, count_distinct: None,
+            distinct_values: Vec::new(), facets: BTreeMap::new()};
+        match json {
+            & Json::Null => return Ok(stats),
+            & Json::Object(_) => (),
+            _ => return Err(format!("SolrQueryResponse JSON parsing error (stats): {} is not an object", name))
+        }
+        if let Some(min) = json.find("min") {
+            stats.min = Some(try!(SolrQueryResponse::parse_value(min)));
+        }
+        if let Some(max) = json.find("max") {
+            stats.max = Some(try!(SolrQueryResponse::parse_value(max)));
+        }
+        stats.sum = json.find("sum").and_then(|x| x.as_f64());
+        stats.count = json.find("count").and_then(|x| x.as_u64());
+        stats.missing = json.find("missing").and_then(|x| x.as_u64());
+        stats.mean = json.find("mean").and_then(|x| x.as_f64());
+        stats.stddev = json.find("stddev").and_then(|x| x.as_f64());
+        stats.sum_of_squares = json.find("sumOfSquares").and_then(|x| x.as_f64());
+        stats.cardinality = json.find("cardinality").and_then(|x| x.as_u64());
+        stats.count_distinct = json.find("countDistinct").and_then(|x| x.as_u64());
+        match json.find("percentiles") {
+            Some(&Json::Array(ref list)) => {
+                for pair in list.chunks(2) {
+                    match (pair[0].as_string().and_then(|x| x.parse().ok()), pair.get(1).and_then(|x| x.as_f64())) {
+                        (Some(percentile), Some(value)) => stats.percentiles.push((percentile, value)),
+                        _ => return Err(format!("SolrQueryResponse JSON parsing error (stats): invalid percentiles for {}", name))
+                    }
+                }
+            },
+            Some(&Json::Object(ref tm)) => {
+                for (percentile, value) in tm.iter() {
+                    match (percentile.parse().ok(), value.as_f64()) {
+                        (Some(p), Some(v)) => stats.percentiles.push((p, v)),
+                        _ => return Err(format!("SolrQueryResponse JSON parsing error (stats): invalid percentiles for {}", name))
+                    }
+                }
+                // keys of the object are sorted as strings, "10.0" before "5.0"
+                stats.percentiles.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            },
+            Some(_) => return Err(format!("SolrQueryResponse JSON parsing error (stats): percentiles is not a JSON list for {}", name)),
+            None => ()
+        }
+        if let Some(&Json::Array(ref list)) = json.find("distinctValues") {
+            for value in list.iter() {
+                stats.distinct_values.push(try!(SolrQueryResponse::parse_value(value)));
+            }
+        }
+        match json.find("facets") {
+            Some(&Json::Object(ref tm)) => {
+                for (facet_field, values_json) in tm.iter() {
+                    let mut values = BTreeMap::new();
+                    match values_json {
+                        & Json::Object(ref values_tm) => {
+                            for (value, value_stats) in values_tm.iter() {
+                                values.insert(value.clone(), try!(FieldStats::from_json(name, value_stats)));
+                            }
+                        },
+                        _ => return Err(format!("SolrQueryResponse JSON parsing error (stats): facet {} is not an object for {}", facet_field, name))
+                    }
+                    stats.facets.insert(facet_field.clone(), values);
+                }
+            },
+            Some(_) => return Err(format!("SolrQueryResponse JSON parsing error (stats): facets is not an object for {}", name)),
+            None => ()
+        }
+        Ok(stats)
+    }
+}
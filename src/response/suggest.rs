@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::{self, Json};
+use response::{SolrError, SolrErrorKind};
+
+pub type SolrSuggestResult = Result<SolrSuggestResponse, SolrError>;
+
+/// Single suggestion of a dictionary
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub term: String,
+    pub weight: i64,
+    /// Payload of the suggestion, `None` when the dictionary has no payload field
+    pub payload: Option<String>
+}
+
+/// Suggestions of a single dictionary
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestResult {
+    /// Query the suggestions were made for
+    pub query: String,
+    pub num_found: u64,
+    pub suggestions: Vec<Suggestion>
+}
+
+#[derive(Debug)]
+pub struct SolrSuggestResponse {
+    /// HTTP status.
+    /// When failed to connect, it will be 0 (zero).
+    pub status: u32,
+    /// Time it took to execute the request in milliseconds
+    pub time: u32,
+    /// Suggestions by dictionary name, one result per query, empty when only building or reloading
+    pub dictionaries: BTreeMap<String, Vec<SuggestResult>>
+}
+
+/* Example JSON of suggest response:
+```ignore
+{
+  "responseHeader": {"status": 0, "QTime": 2},
+  "suggest": {
+    "titleSuggester": {
+      "drag": {
+        "numFound": 2,
+        "suggestions": [
+          {"term": "dragon", "weight": 12, "payload": ""},
+          {"term": "dragonfly", "weight": 3, "payload": ""}
+        ]
+      }
+    }
+  }
+}
+```
+*/
+impl SolrSuggestResponse {
+    /// Finds suggestions of a dictionary for the first query
+    pub fn dictionary(&self, name: &str) -> Option<&SuggestResult> {
+        self.dictionaries.get(name).and_then(|x| x.first())
+    }
+
+    /// Deserializes SolrSuggestResponse from JSON string
+    pub fn from_json_str(json_str: &str) -> SolrSuggestResult {
+        let json = match Json::from_str(json_str) {
+            Ok(j) => j,
+            Err(_) => return Err(suggest_error("SolrSuggestResponse JSON parsing error".to_string()))
+        };
+        if json.find("error").is_some() {
+            return match json::decode::<SolrError>(json_str) {
+                Ok(err) => Err(err),
+                Err(err) => Err(suggest_error(format!("Parse error: {}", err)))
+            };
+        }
+        let (status, time) = match json.find("responseHeader") {
+            Some(rh) => match (rh.find("status").and_then(|x| x.as_u64()), rh.find("QTime").and_then(|x| x.as_u64())) {
+                (Some(status), Some(time)) => (status as u32, time as u32),
+                _ => return Err(suggest_error("SolrSuggestResponse JSON parsing error (responseHeader): status or QTime not found".to_string()))
+            },
+            None => return Err(suggest_error("SolrSuggestResponse JSON parsing error: responseHeader not found".to_string()))
+        };
+        let mut dictionaries = BTreeMap::new();
+        match json.find("suggest") {
+            Some(&Json::Object(ref tm)) => {
+                for (name, dictionary_json) in tm.iter() {
+                    match dictionary_json {
+                        & Json::Object(ref queries) => {
+                            let mut results = Vec::with_capacity(queries.len());
+                            for (query, result_json) in queries.iter() {
+                                results.push(try!(parse_result(query, result_json).map_err(suggest_error)));
+                            }
+                            dictionaries.insert(name.clone(), results);
+                        },
+                        _ => return Err(suggest_error(format!("SolrSuggestResponse JSON parsing error (suggest): {} is not an object", name)))
+                    }
+                }
+            },
+            Some(_) => return Err(suggest_error("SolrSuggestResponse JSON parsing error: suggest is not an object".to_string())),
+            None => ()
+        }
+        Ok(SolrSuggestResponse{status: status, time: time, dictionaries: dictionaries})
+    }
+}
+
+fn suggest_error(message: String) -> SolrError {
+    SolrError{time: 0, status: 0, message: message, kind: SolrErrorKind::Other}
+}
+
+fn parse_result(query: &str, json: &Json) -> Result<SuggestResult, String> {
+    let mut suggestions = Vec::new();
+    match json.find("suggestions") {
+        Some(&Json::Array(ref list)) => {
+            for suggestion_json in list.iter() {
+                let term = match suggestion_json.find("term").and_then(|x| x.as_string()) {
+                    Some(t) => t.to_string(),
+                    None => return Err(format!("SolrSuggestResponse JSON parsing error (suggest): term not found for {}", query))
+                };
+                let payload = match suggestion_json.find("payload").and_then(|x| x.as_string()) {
+                    Some("") | None => None,
+                    Some(p) => Some(p.to_string())
+                };
+                suggestions.push(Suggestion{term: term,
+                    weight: suggestion_json.find("weight").and_then(|x| x.as_i64()).unwrap_or(0),
+                    payload: payload});
+            }
+        },
+        _ => return Err(format!("SolrSuggestResponse JSON parsing error (suggest): suggestions not found for {}", query))
+    }
+    Ok(SuggestResult{query: query.to_string(),
+        num_found: json.find("numFound").and_then(|x| x.as_u64()).unwrap_or(suggestions.len() as u64),
+        suggestions: suggestions})
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use rustc_serialize::json::Json;
+use response::{SolrError, SolrErrorKind};
+
+pub type SolrTermsResult = Result<SolrTermsResponse, SolrError>;
+
+/// Indexed term with its document frequency
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermFrequency {
+    pub term: String,
+    pub frequency: u64
+}
+
+#[derive(Debug)]
+pub struct SolrTermsResponse {
+    /// HTTP status.
+    /// When failed to connect, it will be 0 (zero).
+    pub status: u32,
+    /// Time it took to execute the request in milliseconds
+    pub time: u32,
+    /// Terms by field name, sorted by name, each field keeping the term order returned by Solr
+    pub fields: BTreeMap<String, Vec<TermFrequency>>,
+    /// Number of documents in the index, present only when terms.stats was requested
+    pub num_docs: Option<u64>
+}
+
+/* Example JSON of terms response:
+```ignore
+{
+  "responseHeader": {"status": 0, "QTime": 1},
+  "terms": {
+    "cat": ["electronics", 14, "currency", 4, "memory", 3]
+  },
+  "indexstats": {"numDocs": 32}
+}
+```
+*/
+impl SolrTermsResponse {
+    /// Finds terms of a field
+    pub fn field(&self, name: &str) -> Option<&Vec<TermFrequency>> {
+        self.fields.get(name)
+    }
+
+    /// Deserializes SolrTermsResponse from JSON string
+    pub fn from_json_str(json_str: &str) -> SolrTermsResult {
+        let json = match Json::from_str(json_str) {
+            Ok(j) => j,
+            Err(_) => return Err(terms_error("SolrTermsResponse JSON parsing error".to_string()))
+        };
+        let (status, time) = match json.find("responseHeader") {
+            Some(rh) => match (rh.find("status").and_then(|x| x.as_u64()), rh.find("QTime").and_then(|x| x.as_u64())) {
+                (Some(status), Some(time)) => (status as u32, time as u32),
+                _ => return Err(terms_error("SolrTermsResponse JSON parsing error (responseHeader): status or QTime not found".to_string()))
+            },
+            None => return Err(terms_error("SolrTermsResponse JSON parsing error: responseHeader not found".to_string()))
+        };
+        let mut fields = BTreeMap::new();
+        match json.find("terms") {
+            Some(&Json::Object(ref tm)) => {
+                for (field, terms_json) in tm.iter() {
+                    let mut terms = Vec::new();
+                    match terms_json {
+                        & Json::Array(ref list) => {
+                            for pair in list.chunks(2) {
+                                match (pair[0].as_string(), pair.get(1).and_then(|x| x.as_u64())) {
+                                    (Some(term), Some(frequency)) => terms.push(TermFrequency{term: term.to_string(), frequency: frequency}),
+                                    _ => return Err(terms_error(format!("SolrTermsResponse JSON parsing error (terms): {} is not flat term/frequency pairs", field)))
+                                }
+                            }
+                        },
+                        _ => return Err(terms_error(format!("SolrTermsResponse JSON parsing error (terms): {} is not a JSON list", field)))
+                    }
+                    fields.insert(field.clone(), terms);
+                }
+            },
+            Some(_) => return Err(terms_error("SolrTermsResponse JSON parsing error: terms is not an object".to_string())),
+            None => return Err(terms_error("SolrTermsResponse JSON parsing error: terms not found".to_string()))
+        }
+        Ok(SolrTermsResponse{status: status, time: time, fields: fields,
+            num_docs: json.find_path(&["indexstats", "numDocs"]).and_then(|x| x.as_u64())})
+    }
+}
+
+fn terms_error(message: String) -> SolrError {
+    SolrError{time: 0, status: 0, message: message, kind: SolrErrorKind::Other}
+}
@@ -1,6 +1,6 @@
 use rustc_serialize::{json, Decodable, Decoder};
 use rustc_serialize::json::Json;
-use response::SolrError;
+use response::{SolrError, SolrErrorKind};
 
 pub type SolrUpdateResult = Result<SolrUpdateResponse, SolrError>;
 
@@ -25,3 +25,22 @@ impl Decodable for SolrUpdateResponse {
         })
     }
 }
+
+impl SolrUpdateResponse {
+    /// Parses update response, Solr errors such as version conflicts are returned as SolrError
+    pub fn from_json_str(json_str: &str) -> SolrUpdateResult {
+        let is_error = match Json::from_str(json_str) {
+            Ok(j) => j.find("error").is_some(),
+            Err(_) => false
+        };
+        if is_error {
+            match json::decode::<SolrError>(json_str) {
+                Ok(err) => Err(err),
+                Err(err) => Err(SolrError{status: 0, time: 0, message: format!("Parse error: {}", err), kind: SolrErrorKind::Other})
+            }
+        } else {
+            json::decode::<SolrUpdateResponse>(json_str)
+                .map_err(|err| SolrError{status: 0, time: 0, message: format!("Parse error: {}", err), kind: SolrErrorKind::Other})
+        }
+    }
+}
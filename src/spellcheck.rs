@@ -0,0 +1,117 @@
+/// Represents spellcheck component options (spellcheck.*).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Spellcheck {
+    pub query: Option<String>,
+    pub dictionaries: Vec<String>,
+    pub count: Option<u32>,
+    pub collate: Option<bool>,
+    pub max_collations: Option<u32>,
+    pub max_collation_tries: Option<u32>,
+    pub collate_extended_results: Option<bool>,
+    pub extended_results: Option<bool>,
+    pub only_more_popular: Option<bool>
+}
+
+impl Spellcheck {
+    /// Creates spellcheck options with Solr defaults
+    pub fn new() -> Spellcheck {
+        Spellcheck::default()
+    }
+
+    /// Sets query to spellcheck instead of the main query (spellcheck.q)
+    pub fn query(&self, query: &str) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.query = Some(query.to_string());
+        spellcheck
+    }
+
+    /// Adds dictionary to use (spellcheck.dictionary), multiple dictionaries are combined
+    pub fn add_dictionary(&self, dictionary: &str) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.dictionaries.push(dictionary.to_string());
+        spellcheck
+    }
+
+    /// Sets maximum number of suggestions per term (spellcheck.count)
+    pub fn count(&self, count: u32) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.count = Some(count);
+        spellcheck
+    }
+
+    /// Sets whether corrected queries are built from the suggestions (spellcheck.collate)
+    pub fn collate(&self, collate: bool) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.collate = Some(collate);
+        spellcheck
+    }
+
+    /// Sets maximum number of collations returned (spellcheck.maxCollations)
+    pub fn max_collations(&self, max_collations: u32) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.max_collations = Some(max_collations);
+        spellcheck
+    }
+
+    /// Sets number of collations tested against the index (spellcheck.maxCollationTries),
+    /// only collations with hits are returned when greater than zero
+    pub fn max_collation_tries(&self, max_collation_tries: u32) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.max_collation_tries = Some(max_collation_tries);
+        spellcheck
+    }
+
+    /// Sets whether collations are returned with hit counts and corrections
+    /// (spellcheck.collateExtendedResults)
+    pub fn collate_extended_results(&self, collate_extended_results: bool) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.collate_extended_results = Some(collate_extended_results);
+        spellcheck
+    }
+
+    /// Sets whether suggestions are returned with frequencies (spellcheck.extendedResults)
+    pub fn extended_results(&self, extended_results: bool) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.extended_results = Some(extended_results);
+        spellcheck
+    }
+
+    /// Sets whether only suggestions more frequent than the original term are returned
+    /// (spellcheck.onlyMorePopular)
+    pub fn only_more_popular(&self, only_more_popular: bool) -> Spellcheck {
+        let mut spellcheck = self.clone();
+        spellcheck.only_more_popular = Some(only_more_popular);
+        spellcheck
+    }
+
+    /// Converts these options to URL pairs, including spellcheck=true
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("spellcheck".to_string(), "true".to_string()));
+        if let Some(ref query) = self.query {
+            vec.push(("spellcheck.q".to_string(), query.clone()));
+        }
+        vec.extend(self.dictionaries.iter().map(|x| ("spellcheck.dictionary".to_string(), x.clone())));
+        if let Some(count) = self.count {
+            vec.push(("spellcheck.count".to_string(), count.to_string()));
+        }
+        if let Some(collate) = self.collate {
+            vec.push(("spellcheck.collate".to_string(), collate.to_string()));
+        }
+        if let Some(max_collations) = self.max_collations {
+            vec.push(("spellcheck.maxCollations".to_string(), max_collations.to_string()));
+        }
+        if let Some(max_collation_tries) = self.max_collation_tries {
+            vec.push(("spellcheck.maxCollationTries".to_string(), max_collation_tries.to_string()));
+        }
+        if let Some(collate_extended_results) = self.collate_extended_results {
+            vec.push(("spellcheck.collateExtendedResults".to_string(), collate_extended_results.to_string()));
+        }
+        if let Some(extended_results) = self.extended_results {
+            vec.push(("spellcheck.extendedResults".to_string(), extended_results.to_string()));
+        }
+        if let Some(only_more_popular) = self.only_more_popular {
+            vec.push(("spellcheck.onlyMorePopular".to_string(), only_more_popular.to_string()));
+        }
+        vec
+    }
+}
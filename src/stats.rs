@@ -0,0 +1,130 @@
+use query::ToUrlParam;
+use query_dsl::LocalParams;
+
+/// Represents a single statistic computed by the stats component
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stat {
+    Min,
+    Max,
+    Sum,
+    Count,
+    Missing,
+    Mean,
+    Stddev,
+    SumOfSquares,
+    CountDistinct,
+    DistinctValues
+}
+
+impl ToUrlParam for Stat {
+    fn to_url_param(&self) -> String {
+        match *self {
+            Stat::Min => "min".to_string(),
+            Stat::Max => "max".to_string(),
+            Stat::Sum => "sum".to_string(),
+            Stat::Count => "count".to_string(),
+            Stat::Missing => "missing".to_string(),
+            Stat::Mean => "mean".to_string(),
+            Stat::Stddev => "stddev".to_string(),
+            Stat::SumOfSquares => "sumOfSquares".to_string(),
+            Stat::CountDistinct => "countDistinct".to_string(),
+            Stat::DistinctValues => "distinctValues".to_string()
+        }
+    }
+}
+
+/// Represents statistics of a single field (stats.field).
+/// When no statistic is selected, Solr computes all the default ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatsField {
+    pub field: String,
+    pub stats: Vec<Stat>,
+    pub percentiles: Vec<f64>,
+    /// Cardinality estimation accuracy, between 0.0 and 1.0
+    pub cardinality: Option<f64>,
+    /// Fields to compute statistics for each of their values (f.<field>.stats.facet)
+    pub facets: Vec<String>,
+    /// Tags of filters to exclude when computing ({!ex=...})
+    pub exclude: Vec<String>,
+    /// Label used in the response instead of the field name ({!key=...})
+    pub key: Option<String>
+}
+
+impl StatsField {
+    /// Creates statistics of the given field or function
+    pub fn new(field: &str) -> StatsField {
+        StatsField{field: field.to_string(), stats: Vec::new(), percentiles: Vec::new(), cardinality: None,
+            facets: Vec::new(), exclude: Vec::new(), key: None}
+    }
+
+    /// Adds statistic to compute, for example `{!min=true}`
+    pub fn add_stat(&self, stat: Stat) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.stats.push(stat);
+        stats_field
+    }
+
+    /// Sets percentiles to compute, for example `{!percentiles='50,99'}`
+    pub fn percentiles(&self, percentiles: &[f64]) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.percentiles = percentiles.to_vec();
+        stats_field
+    }
+
+    /// Enables cardinality estimation with given accuracy, between 0.0 and 1.0 (`{!cardinality=...}`)
+    pub fn cardinality(&self, accuracy: f64) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.cardinality = Some(accuracy);
+        stats_field
+    }
+
+    /// Adds field to compute statistics for each of its values (f.<field>.stats.facet)
+    pub fn add_facet(&self, field: &str) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.facets.push(field.to_string());
+        stats_field
+    }
+
+    /// Excludes filters tagged with tag when computing
+    pub fn exclude(&self, tag: &str) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.exclude.push(tag.to_string());
+        stats_field
+    }
+
+    /// Sets label used in the response instead of the field name
+    pub fn key(&self, key: &str) -> StatsField {
+        let mut stats_field = self.clone();
+        stats_field.key = Some(key.to_string());
+        stats_field
+    }
+
+    /// Converts these statistics to URL pairs: stats.field followed by per-field stats.facet
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut local_params = LocalParams::new(None);
+        for stat in self.stats.iter() {
+            local_params = local_params.param(&stat.to_url_param(), "true");
+        }
+        if !self.percentiles.is_empty() {
+            let percentiles: Vec<String> = self.percentiles.iter().map(|x| x.to_string()).collect();
+            local_params = local_params.param("percentiles", &percentiles.join(","));
+        }
+        if let Some(cardinality) = self.cardinality {
+            local_params = local_params.param("cardinality", &cardinality.to_string());
+        }
+        if !self.exclude.is_empty() {
+            local_params = local_params.param("ex", &self.exclude.join(","));
+        }
+        if let Some(ref key) = self.key {
+            local_params = local_params.param("key", key);
+        }
+        let stats_field = if local_params.params.is_empty() {
+            self.field.clone()
+        } else {
+            format!("{}{}", local_params, self.field)
+        };
+        let mut vec = vec!(("stats.field".to_string(), stats_field));
+        vec.extend(self.facets.iter().map(|x| (format!("f.{}.stats.facet", self.field), x.clone())));
+        vec
+    }
+}
@@ -0,0 +1,101 @@
+/// Represents a request to the suggester request handler (/suggest by default).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuggestQuery {
+    pub query: Option<String>,
+    /// Path of the request handler relative to the core URL
+    pub handler: String,
+    pub dictionaries: Vec<String>,
+    pub count: Option<u32>,
+    pub cfq: Option<String>,
+    pub build: Option<bool>,
+    pub reload: Option<bool>,
+    pub build_all: Option<bool>
+}
+
+impl SuggestQuery {
+    /// Creates a new suggest request for the prefix typed by user (suggest.q)
+    pub fn new(query: &str) -> SuggestQuery {
+        SuggestQuery{query: Some(query.to_string()), .. SuggestQuery::empty()}
+    }
+
+    /// Creates a new suggest request without query, used for building or reloading dictionaries
+    pub fn empty() -> SuggestQuery {
+        SuggestQuery{query: None, handler: "suggest".to_string(), dictionaries: Vec::new(),
+            count: None, cfq: None, build: None, reload: None, build_all: None}
+    }
+
+    /// Sets path of the request handler relative to the core URL, `suggest` by default
+    pub fn handler(&self, handler: &str) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.handler = handler.to_string();
+        suggest
+    }
+
+    /// Adds dictionary to get suggestions from (suggest.dictionary)
+    pub fn add_dictionary(&self, dictionary: &str) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.dictionaries.push(dictionary.to_string());
+        suggest
+    }
+
+    /// Sets maximum number of suggestions per dictionary (suggest.count)
+    pub fn count(&self, count: u32) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.count = Some(count);
+        suggest
+    }
+
+    /// Sets context filter query, filtering suggestions by the context field (suggest.cfq)
+    pub fn cfq(&self, cfq: &str) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.cfq = Some(cfq.to_string());
+        suggest
+    }
+
+    /// Sets whether the dictionaries are built before suggesting (suggest.build)
+    pub fn build(&self, build: bool) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.build = Some(build);
+        suggest
+    }
+
+    /// Sets whether the dictionaries are reloaded before suggesting (suggest.reload)
+    pub fn reload(&self, reload: bool) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.reload = Some(reload);
+        suggest
+    }
+
+    /// Sets whether all dictionaries are built, not only the requested ones (suggest.buildAll)
+    pub fn build_all(&self, build_all: bool) -> SuggestQuery {
+        let mut suggest = self.clone();
+        suggest.build_all = Some(build_all);
+        suggest
+    }
+
+    /// Converts this request to a vector of pairs, suitable for URL percent encoding
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("wt".to_string(), "json".to_string()),
+                           ("suggest".to_string(), "true".to_string()));
+        if let Some(ref query) = self.query {
+            vec.push(("suggest.q".to_string(), query.clone()));
+        }
+        vec.extend(self.dictionaries.iter().map(|x| ("suggest.dictionary".to_string(), x.clone())));
+        if let Some(count) = self.count {
+            vec.push(("suggest.count".to_string(), count.to_string()));
+        }
+        if let Some(ref cfq) = self.cfq {
+            vec.push(("suggest.cfq".to_string(), cfq.clone()));
+        }
+        if let Some(build) = self.build {
+            vec.push(("suggest.build".to_string(), build.to_string()));
+        }
+        if let Some(reload) = self.reload {
+            vec.push(("suggest.reload".to_string(), reload.to_string()));
+        }
+        if let Some(build_all) = self.build_all {
+            vec.push(("suggest.buildAll".to_string(), build_all.to_string()));
+        }
+        vec
+    }
+}
@@ -0,0 +1,168 @@
+use query::ToUrlParam;
+
+/// Represents order of returned terms (terms.sort)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TermsSort {
+    /// Most frequent terms first
+    Count,
+    /// Terms in index order
+    Index
+}
+
+impl ToUrlParam for TermsSort {
+    fn to_url_param(&self) -> String {
+        match *self {
+            TermsSort::Count => "count".to_string(),
+            TermsSort::Index => "index".to_string()
+        }
+    }
+}
+
+/// Represents a request to the terms request handler (/terms by default),
+/// listing indexed terms of fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermsQuery {
+    /// Path of the request handler relative to the core URL
+    pub handler: String,
+    pub fields: Vec<String>,
+    pub prefix: Option<String>,
+    pub regex: Option<String>,
+    pub lower: Option<String>,
+    pub lower_incl: Option<bool>,
+    pub upper: Option<String>,
+    pub upper_incl: Option<bool>,
+    pub mincount: Option<u32>,
+    pub maxcount: Option<u32>,
+    pub limit: Option<i32>,
+    pub sort: Option<TermsSort>,
+    pub stats: Option<bool>
+}
+
+impl TermsQuery {
+    /// Creates a new terms request for the given field (terms.fl)
+    pub fn new(field: &str) -> TermsQuery {
+        TermsQuery{handler: "terms".to_string(), fields: vec!(field.to_string()), prefix: None, regex: None,
+            lower: None, lower_incl: None, upper: None, upper_incl: None, mincount: None, maxcount: None,
+            limit: None, sort: None, stats: None}
+    }
+
+    /// Sets path of the request handler relative to the core URL, `terms` by default
+    pub fn handler(&self, handler: &str) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.handler = handler.to_string();
+        terms
+    }
+
+    /// Adds another field to list terms of (terms.fl)
+    pub fn add_field(&self, field: &str) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.fields.push(field.to_string());
+        terms
+    }
+
+    /// Restricts terms to those starting with prefix (terms.prefix)
+    pub fn prefix(&self, prefix: &str) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.prefix = Some(prefix.to_string());
+        terms
+    }
+
+    /// Restricts terms to those matching regular expression (terms.regex)
+    pub fn regex(&self, regex: &str) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.regex = Some(regex.to_string());
+        terms
+    }
+
+    /// Sets term to start at (terms.lower) and whether it's included (terms.lower.incl)
+    pub fn lower(&self, lower: &str, inclusive: bool) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.lower = Some(lower.to_string());
+        terms.lower_incl = Some(inclusive);
+        terms
+    }
+
+    /// Sets term to stop at (terms.upper) and whether it's included (terms.upper.incl)
+    pub fn upper(&self, upper: &str, inclusive: bool) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.upper = Some(upper.to_string());
+        terms.upper_incl = Some(inclusive);
+        terms
+    }
+
+    /// Sets minimum document frequency of returned terms (terms.mincount)
+    pub fn mincount(&self, mincount: u32) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.mincount = Some(mincount);
+        terms
+    }
+
+    /// Sets maximum document frequency of returned terms (terms.maxcount)
+    pub fn maxcount(&self, maxcount: u32) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.maxcount = Some(maxcount);
+        terms
+    }
+
+    /// Sets maximum number of terms per field, negative means unlimited (terms.limit)
+    pub fn limit(&self, limit: i32) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.limit = Some(limit);
+        terms
+    }
+
+    /// Sets order of returned terms (terms.sort)
+    pub fn sort(&self, sort: TermsSort) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.sort = Some(sort);
+        terms
+    }
+
+    /// Sets whether index statistics are returned (terms.stats)
+    pub fn stats(&self, stats: bool) -> TermsQuery {
+        let mut terms = self.clone();
+        terms.stats = Some(stats);
+        terms
+    }
+
+    /// Converts this request to a vector of pairs, suitable for URL percent encoding
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("wt".to_string(), "json".to_string()),
+                           ("terms".to_string(), "true".to_string()));
+        vec.extend(self.fields.iter().map(|x| ("terms.fl".to_string(), x.clone())));
+        if let Some(ref prefix) = self.prefix {
+            vec.push(("terms.prefix".to_string(), prefix.clone()));
+        }
+        if let Some(ref regex) = self.regex {
+            vec.push(("terms.regex".to_string(), regex.clone()));
+        }
+        if let Some(ref lower) = self.lower {
+            vec.push(("terms.lower".to_string(), lower.clone()));
+        }
+        if let Some(lower_incl) = self.lower_incl {
+            vec.push(("terms.lower.incl".to_string(), lower_incl.to_string()));
+        }
+        if let Some(ref upper) = self.upper {
+            vec.push(("terms.upper".to_string(), upper.clone()));
+        }
+        if let Some(upper_incl) = self.upper_incl {
+            vec.push(("terms.upper.incl".to_string(), upper_incl.to_string()));
+        }
+        if let Some(mincount) = self.mincount {
+            vec.push(("terms.mincount".to_string(), mincount.to_string()));
+        }
+        if let Some(maxcount) = self.maxcount {
+            vec.push(("terms.maxcount".to_string(), maxcount.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            vec.push(("terms.limit".to_string(), limit.to_string()));
+        }
+        if let Some(ref sort) = self.sort {
+            vec.push(("terms.sort".to_string(), sort.to_url_param()));
+        }
+        if let Some(stats) = self.stats {
+            vec.push(("terms.stats".to_string(), stats.to_string()));
+        }
+        vec
+    }
+}
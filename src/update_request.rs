@@ -0,0 +1,207 @@
+use rustc_serialize::{Encodable, Encoder};
+use document::SolrDocument;
+use atomic_update::AtomicUpdate;
+use query_dsl::ToQueryString;
+
+/// Single command of an update request
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdateCommand {
+    Add(SolrDocument),
+    /// Atomic update of an existing document
+    Update(AtomicUpdate),
+    DeleteByIds(Vec<String>),
+    DeleteByQuery(String)
+}
+
+/// Represents update request combining adds, atomic updates and deletes in a single JSON body,
+/// with commit options sent as request parameters.
+/// Unlike `SolrClient::delete_by_*`, nothing is committed unless requested.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateRequest {
+    pub commands: Vec<UpdateCommand>,
+    pub commit: Option<bool>,
+    pub soft_commit: Option<bool>,
+    pub commit_within: Option<u32>,
+    pub wait_searcher: Option<bool>,
+    pub open_searcher: Option<bool>,
+    pub overwrite: Option<bool>,
+    pub expunge_deletes: Option<bool>,
+    pub optimize: Option<bool>,
+    pub max_segments: Option<u32>,
+    pub update_chain: Option<String>
+}
+
+impl UpdateRequest {
+    /// Creates empty update request
+    pub fn new() -> UpdateRequest {
+        UpdateRequest::default()
+    }
+
+    /// Adds a document
+    pub fn add(&self, document: &SolrDocument) -> UpdateRequest {
+        self.command(UpdateCommand::Add(document.clone()))
+    }
+
+    /// Adds multiple documents
+    pub fn add_many(&self, documents: &[&SolrDocument]) -> UpdateRequest {
+        let mut request = self.clone();
+        request.commands.extend(documents.iter().map(|x| UpdateCommand::Add((*x).clone())));
+        request
+    }
+
+    /// Atomically updates fields of an existing document
+    pub fn update_fields(&self, update: &AtomicUpdate) -> UpdateRequest {
+        self.command(UpdateCommand::Update(update.clone()))
+    }
+
+    /// Deletes a document by unique ID
+    pub fn delete_by_id(&self, id: &str) -> UpdateRequest {
+        self.command(UpdateCommand::DeleteByIds(vec!(id.to_string())))
+    }
+
+    /// Deletes documents by unique IDs
+    pub fn delete_by_ids(&self, ids: &[&str]) -> UpdateRequest {
+        self.command(UpdateCommand::DeleteByIds(ids.iter().map(|x| x.to_string()).collect()))
+    }
+
+    /// Deletes documents matching query
+    pub fn delete_by_query<Q: ToQueryString + ?Sized>(&self, query: &Q) -> UpdateRequest {
+        self.command(UpdateCommand::DeleteByQuery(query.to_query_string()))
+    }
+
+    fn command(&self, command: UpdateCommand) -> UpdateRequest {
+        let mut request = self.clone();
+        request.commands.push(command);
+        request
+    }
+
+    /// Performs hard commit after the update
+    pub fn commit(&self) -> UpdateRequest {
+        let mut request = self.clone();
+        request.commit = Some(true);
+        request
+    }
+
+    /// Performs soft commit after the update, making changes visible without flushing them to disk
+    pub fn soft_commit(&self) -> UpdateRequest {
+        let mut request = self.clone();
+        request.soft_commit = Some(true);
+        request
+    }
+
+    /// Commits changes within the given number of milliseconds
+    pub fn commit_within(&self, milliseconds: u32) -> UpdateRequest {
+        let mut request = self.clone();
+        request.commit_within = Some(milliseconds);
+        request
+    }
+
+    /// Whether commit waits for a new searcher to be opened and registered
+    pub fn wait_searcher(&self, wait_searcher: bool) -> UpdateRequest {
+        let mut request = self.clone();
+        request.wait_searcher = Some(wait_searcher);
+        request
+    }
+
+    /// Whether hard commit opens a new searcher, making changes visible
+    pub fn open_searcher(&self, open_searcher: bool) -> UpdateRequest {
+        let mut request = self.clone();
+        request.open_searcher = Some(open_searcher);
+        request
+    }
+
+    /// Whether added documents replace existing ones with the same unique key, true by default
+    pub fn overwrite(&self, overwrite: bool) -> UpdateRequest {
+        let mut request = self.clone();
+        request.overwrite = Some(overwrite);
+        request
+    }
+
+    /// Whether commit merges away segments with deleted documents
+    pub fn expunge_deletes(&self, expunge_deletes: bool) -> UpdateRequest {
+        let mut request = self.clone();
+        request.expunge_deletes = Some(expunge_deletes);
+        request
+    }
+
+    /// Optimizes the index after the update, merging segments
+    pub fn optimize(&self) -> UpdateRequest {
+        let mut request = self.clone();
+        request.optimize = Some(true);
+        request
+    }
+
+    /// Number of segments to merge down to when optimizing
+    pub fn max_segments(&self, max_segments: u32) -> UpdateRequest {
+        let mut request = self.clone();
+        request.max_segments = Some(max_segments);
+        request
+    }
+
+    /// Name of the update request processor chain (update.chain)
+    pub fn update_chain(&self, chain: &str) -> UpdateRequest {
+        let mut request = self.clone();
+        request.update_chain = Some(chain.to_string());
+        request
+    }
+
+    /// Converts options to request parameters
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("wt".to_string(), "json".to_string()));
+        if let Some(commit) = self.commit {
+            vec.push(("commit".to_string(), commit.to_string()));
+        }
+        if let Some(soft_commit) = self.soft_commit {
+            vec.push(("softCommit".to_string(), soft_commit.to_string()));
+        }
+        if let Some(commit_within) = self.commit_within {
+            vec.push(("commitWithin".to_string(), commit_within.to_string()));
+        }
+        if let Some(wait_searcher) = self.wait_searcher {
+            vec.push(("waitSearcher".to_string(), wait_searcher.to_string()));
+        }
+        if let Some(open_searcher) = self.open_searcher {
+            vec.push(("openSearcher".to_string(), open_searcher.to_string()));
+        }
+        if let Some(overwrite) = self.overwrite {
+            vec.push(("overwrite".to_string(), overwrite.to_string()));
+        }
+        if let Some(expunge_deletes) = self.expunge_deletes {
+            vec.push(("expungeDeletes".to_string(), expunge_deletes.to_string()));
+        }
+        if let Some(optimize) = self.optimize {
+            vec.push(("optimize".to_string(), optimize.to_string()));
+        }
+        if let Some(max_segments) = self.max_segments {
+            vec.push(("maxSegments".to_string(), max_segments.to_string()));
+        }
+        if let Some(ref update_chain) = self.update_chain {
+            vec.push(("update.chain".to_string(), update_chain.clone()));
+        }
+        vec
+    }
+}
+
+// Encodes commands in order as a JSON object with repeated keys,
+// for example {"add":{"doc":{"id":"1"}},"delete":["2"],"delete":{"query":"type:Draft"}}
+impl Encodable for UpdateRequest {
+    fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+        e.emit_struct("UpdateRequest", self.commands.len(), |e| {
+            for (i, command) in self.commands.iter().enumerate() {
+                try!(match *command {
+                    UpdateCommand::Add(ref doc) => e.emit_struct_field("add", i, |e| {
+                        e.emit_struct("add", 1, |e| e.emit_struct_field("doc", 0, |e| doc.encode(e)))
+                    }),
+                    UpdateCommand::Update(ref update) => e.emit_struct_field("add", i, |e| {
+                        e.emit_struct("add", 1, |e| e.emit_struct_field("doc", 0, |e| update.encode(e)))
+                    }),
+                    UpdateCommand::DeleteByIds(ref ids) => e.emit_struct_field("delete", i, |e| ids.encode(e)),
+                    UpdateCommand::DeleteByQuery(ref query) => e.emit_struct_field("delete", i, |e| {
+                        e.emit_struct("delete", 1, |e| e.emit_struct_field("query", 0, |e| query.encode(e)))
+                    })
+                });
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use hyper::header::ContentType;
+
+/// Options of CSV update format
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CsvOptions {
+    pub separator: Option<char>,
+    /// Whether the first line contains field names
+    pub header: Option<bool>,
+    /// Field names, when there is no header or to override it
+    pub fieldnames: Option<Vec<String>>,
+    /// Whether values of all fields are split into multiple values
+    pub split: Option<bool>,
+    /// Fields with values split into multiple values by a separator
+    pub split_fields: Option<Vec<(String, char)>>
+}
+
+impl CsvOptions {
+    pub fn new() -> CsvOptions {
+        CsvOptions::default()
+    }
+
+    /// Separator of values, comma by default
+    pub fn separator(&self, separator: char) -> CsvOptions {
+        let mut options = self.clone();
+        options.separator = Some(separator);
+        options
+    }
+
+    pub fn header(&self, header: bool) -> CsvOptions {
+        let mut options = self.clone();
+        options.header = Some(header);
+        options
+    }
+
+    pub fn fieldnames(&self, fieldnames: &[&str]) -> CsvOptions {
+        let mut options = self.clone();
+        options.fieldnames = Some(fieldnames.iter().map(|x| x.to_string()).collect());
+        options
+    }
+
+    pub fn split(&self, split: bool) -> CsvOptions {
+        let mut options = self.clone();
+        options.split = Some(split);
+        options
+    }
+
+    /// Splits values of a field into multiple values by separator
+    pub fn split_field(&self, field: &str, separator: char) -> CsvOptions {
+        let mut options = self.clone();
+        match options.split_fields {
+            Some(ref mut x) => x.push((field.to_string(), separator)),
+            None => options.split_fields = Some(vec!((field.to_string(), separator)))
+        }
+        options
+    }
+
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = Vec::new();
+        if let Some(separator) = self.separator {
+            vec.push(("separator".to_string(), separator.to_string()));
+        }
+        if let Some(header) = self.header {
+            vec.push(("header".to_string(), header.to_string()));
+        }
+        if let Some(ref fieldnames) = self.fieldnames {
+            vec.push(("fieldnames".to_string(), fieldnames.join(",")));
+        }
+        if let Some(split) = self.split {
+            vec.push(("split".to_string(), split.to_string()));
+        }
+        if let Some(ref split_fields) = self.split_fields {
+            for &(ref field, separator) in split_fields.iter() {
+                vec.push((format!("f.{}.split", field), "true".to_string()));
+                vec.push((format!("f.{}.separator", field), separator.to_string()));
+            }
+        }
+        vec
+    }
+}
+
+/// Format of an update body streamed to Solr
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdateFormat {
+    /// JSON array of documents or JSON update commands
+    Json,
+    /// Newline-delimited JSON documents, sent to /update/json/docs
+    JsonLines,
+    Csv(CsvOptions),
+    /// XML update format, for example <add><doc><field name="id">1</field></doc></add>
+    Xml
+}
+
+impl UpdateFormat {
+    /// Update handler path, relative to the core URL
+    pub fn handler(&self) -> &'static str {
+        match *self {
+            UpdateFormat::JsonLines => "./update/json/docs",
+            _ => "./update"
+        }
+    }
+
+    pub fn content_type(&self) -> ContentType {
+        match *self {
+            UpdateFormat::Json | UpdateFormat::JsonLines => ContentType::json(),
+            UpdateFormat::Csv(_) => ContentType("application/csv".parse().unwrap()),
+            UpdateFormat::Xml => ContentType("application/xml".parse().unwrap())
+        }
+    }
+
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut vec = vec!(("wt".to_string(), "json".to_string()));
+        if let UpdateFormat::Csv(ref options) = *self {
+            vec.extend(options.to_pairs());
+        }
+        vec
+    }
+}
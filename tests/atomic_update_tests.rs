@@ -0,0 +1,63 @@
+extern crate rustc_serialize;
+extern crate heliotrope;
+
+use rustc_serialize::json;
+use heliotrope::{AtomicUpdate, SolrValue, DocumentVersion};
+
+#[test]
+fn atomic_update_with_set_and_inc(){
+    let update = AtomicUpdate::new("1").set("title", "Moby Dick").inc("views", 2);
+    assert_eq!(r#"{"id":"1","title":{"set":"Moby Dick"},"views":{"inc":2}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_with_multi_valued_modifiers(){
+    let update = AtomicUpdate::new("1")
+        .add("tags", vec!("a", "b"))
+        .add_distinct("cat", "book")
+        .remove("tags", "c")
+        .remove_regex("tags", "draft.*");
+    assert_eq!(r#"{"id":"1","tags":{"add":["a","b"],"remove":"c","removeregex":"draft.*"},"cat":{"add-distinct":"book"}}"#,
+               json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_should_merge_repeated_modifiers(){
+    let update = AtomicUpdate::new("1").add("tags", "a").add("tags", vec!("b", "c"));
+    assert_eq!(r#"{"id":"1","tags":{"add":["a","b","c"]}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_should_remove_field_with_null(){
+    let none: Option<f64> = None;
+    let update = AtomicUpdate::new("1").unset("discount").set("price", none);
+    assert_eq!(r#"{"id":"1","discount":{"set":null},"price":{"set":null}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_with_custom_unique_key(){
+    let update = AtomicUpdate::new("X-1").unique_key("sku").set("price", SolrValue::F64(9.5)).inc("stock", -1);
+    assert_eq!(r#"{"sku":"X-1","price":{"set":9.5},"stock":{"inc":-1}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_with_expected_version(){
+    let update = AtomicUpdate::new("1").version(DocumentVersion::Exactly(1628)).inc("views", 1);
+    assert_eq!(r#"{"id":"1","_version_":1628,"views":{"inc":1}}"#, json::encode(&update).unwrap());
+    let update = AtomicUpdate::new("2").version(DocumentVersion::MustNotExist).set("title", "Moby Dick");
+    assert_eq!(r#"{"id":"2","_version_":-1,"title":{"set":"Moby Dick"}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_repeated_set_should_replace_value(){
+    let update = AtomicUpdate::new("1").set("title", "a").inc("views", 1).set("title", "b");
+    assert_eq!(r#"{"id":"1","title":{"set":"b"},"views":{"inc":1}}"#, json::encode(&update).unwrap());
+}
+
+#[test]
+fn atomic_update_repeated_inc_should_sum_amounts(){
+    let update = AtomicUpdate::new("1").inc("views", 1).add("tags", "a").inc("views", 2);
+    assert_eq!(r#"{"id":"1","views":{"inc":3},"tags":{"add":"a"}}"#, json::encode(&update).unwrap());
+    let update = AtomicUpdate::new("1").inc("price", 1).inc("price", -0.5);
+    assert_eq!(r#"{"id":"1","price":{"inc":0.5}}"#, json::encode(&update).unwrap());
+}
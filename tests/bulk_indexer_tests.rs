@@ -0,0 +1,112 @@
+extern crate heliotrope;
+extern crate url;
+
+mod common;
+
+use std::time::Duration;
+use heliotrope::{SolrClient, SolrDocument};
+use common::fake_solr;
+
+static OK: &'static str = r#"{"responseHeader":{"status":0,"QTime":1}}"#;
+
+fn documents(count: usize) -> Vec<SolrDocument> {
+    (0..count).map(|i| {
+        let mut document = SolrDocument::new();
+        document.add_field("id", &i.to_string());
+        document
+    }).collect()
+}
+
+#[test]
+fn bulk_indexer_should_batch_by_count(){
+    let (url, requests) = fake_solr(|_, _| (200, OK.to_string()));
+    let stats = SolrClient::new(&url).bulk_indexer().batch_size(3).workers(2).index(documents(10));
+    assert_eq!(10, stats.documents);
+    assert_eq!(10, stats.indexed);
+    assert_eq!(0, stats.failed);
+    assert_eq!(4, stats.batches);
+    let requests = requests.lock().unwrap();
+    assert_eq!(4, requests.len());
+    assert_eq!(stats.bytes, requests.iter().map(|r| r.body.len() as u64).sum::<u64>());
+    assert!(requests.iter().any(|r| r.body == r#"[{"id":"9"}]"#));
+}
+
+#[test]
+fn bulk_indexer_should_batch_by_bytes(){
+    let (url, requests) = fake_solr(|_, _| (200, OK.to_string()));
+    // [{"id":"0"},{"id":"1"}] is 23 bytes
+    let stats = SolrClient::new(&url).bulk_indexer().batch_bytes(23).workers(1).index(documents(5));
+    assert_eq!(5, stats.indexed);
+    assert_eq!(3, stats.batches);
+    assert_eq!(r#"[{"id":"0"},{"id":"1"}]"#, requests.lock().unwrap()[0].body);
+}
+
+#[test]
+fn bulk_indexer_should_report_rejected_documents(){
+    let (url, _) = fake_solr(|_, request| if request.body.contains(r#""id":"3""#) {
+        (400, r#"{"responseHeader":{"status":400,"QTime":1},"error":{"msg":"ERROR: [doc=3] bad","code":400}}"#.to_string())
+    } else {
+        (200, OK.to_string())
+    });
+    let stats = SolrClient::new(&url).bulk_indexer().batch_size(5).workers(1).index(documents(5));
+    assert_eq!(4, stats.indexed);
+    assert_eq!(1, stats.failed);
+    assert_eq!(Some("3".to_string()), stats.failures[0].id);
+    assert_eq!(400, stats.failures[0].error.status);
+    assert_eq!(0, stats.retries);
+}
+
+#[test]
+fn bulk_indexer_should_retry_server_errors(){
+    let (url, requests) = fake_solr(|n, _| if n == 1 {
+        (503, r#"{"error":{"msg":"Service Unavailable","code":503}}"#.to_string())
+    } else {
+        (200, OK.to_string())
+    });
+    let stats = SolrClient::new(&url).bulk_indexer().workers(1).retry_delay(Duration::from_millis(1)).index(documents(2));
+    assert_eq!(2, stats.indexed);
+    assert_eq!(1, stats.retries);
+    assert_eq!(2, requests.lock().unwrap().len());
+}
+
+#[test]
+fn bulk_indexer_should_give_up_after_max_retries(){
+    let (url, requests) = fake_solr(|_, _| (500, r#"{"error":{"msg":"Server Error","code":500}}"#.to_string()));
+    let stats = SolrClient::new(&url).bulk_indexer().max_retries(2).retry_delay(Duration::from_millis(1))
+        .commit_within(1000).index(documents(3));
+    assert_eq!(0, stats.indexed);
+    assert_eq!(3, stats.failed);
+    assert_eq!(2, stats.retries);
+    assert_eq!(3, requests.lock().unwrap().len());
+}
+
+#[test]
+fn bulk_indexer_should_fail_only_conflicting_document(){
+    // documents before the conflicting one are applied, the rest of the batch is sent again
+    let (url, requests) = fake_solr(|n, _| if n == 1 {
+        (409, r#"{"error":{"msg":"version conflict for 2 expected=-1 actual=1631","code":409}}"#.to_string())
+    } else {
+        (200, OK.to_string())
+    });
+    let stats = SolrClient::new(&url).bulk_indexer().batch_size(5).workers(1).index(documents(5));
+    assert_eq!(4, stats.indexed);
+    assert_eq!(1, stats.failed);
+    assert_eq!(Some("2".to_string()), stats.failures[0].id);
+    let requests = requests.lock().unwrap();
+    assert_eq!(2, requests.len());
+    assert_eq!(r#"[{"id":"3"},{"id":"4"}]"#, requests[1].body);
+}
+
+#[test]
+fn bulk_indexer_should_fail_every_conflicting_document(){
+    // every request conflicts on its first document, [{"id":"2"},...] conflicting on 2
+    let (url, requests) = fake_solr(|_, request| {
+        let id = request.body[8..].split('"').next().unwrap().to_string();
+        (409, format!(r#"{{"error":{{"msg":"version conflict for {} expected=-1 actual=1631","code":409}}}}"#, id))
+    });
+    let stats = SolrClient::new(&url).bulk_indexer().batch_size(50).workers(1).index(documents(50));
+    assert_eq!(0, stats.indexed);
+    assert_eq!(50, stats.failed);
+    assert_eq!(Some("49".to_string()), stats.failures[49].id);
+    assert_eq!(50, requests.lock().unwrap().len());
+}
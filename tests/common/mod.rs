@@ -0,0 +1,78 @@
+use std::io::{Write, BufRead, BufReader};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use url::Url;
+
+/// Request received by fake Solr
+#[allow(dead_code)]
+pub struct Request {
+    pub request_line: String,
+    pub content_type: String,
+    pub body: String
+}
+
+/// Starts fake Solr answering requests with (status, body) chosen by respond, called with
+/// the number of the request counting from 1, returns core URL and received requests
+pub fn fake_solr<F>(respond: F) -> (Url, Arc<Mutex<Vec<Request>>>)
+    where F: Fn(usize, &Request) -> (u16, String) + Send + 'static {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/solr/test/", listener.local_addr().unwrap())).unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let request = read_request(&mut BufReader::new(stream.try_clone().unwrap()));
+            let (status, response) = {
+                let mut requests = received.lock().unwrap();
+                let answer = respond(requests.len() + 1, &request);
+                requests.push(request);
+                answer
+            };
+            let _ = write!(stream, "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                           status, response.len(), response);
+        }
+    });
+    (url, requests)
+}
+
+/// Reads request with a body of known length or sent with chunked transfer encoding
+fn read_request<R: BufRead>(reader: &mut R) -> Request {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let (mut content_type, mut content_length, mut chunked) = (String::new(), 0, false);
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let lower = line.to_lowercase();
+        if lower.starts_with("content-type:") {
+            content_type = line[13..].trim().to_string();
+        } else if lower.starts_with("content-length:") {
+            content_length = line[15..].trim().parse().unwrap();
+        } else if lower.starts_with("transfer-encoding:") && lower.contains("chunked") {
+            chunked = true;
+        }
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+    }
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size).unwrap();
+            let size = usize::from_str_radix(size.trim(), 16).unwrap();
+            let mut chunk = vec![0u8; size + 2];
+            reader.read_exact(&mut chunk).unwrap();
+            if size == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..size]);
+        }
+    } else {
+        body.resize(content_length, 0);
+        reader.read_exact(&mut body).unwrap();
+    }
+    Request{request_line: request_line.trim().to_string(), content_type: content_type, body: String::from_utf8(body).unwrap()}
+}
@@ -0,0 +1,48 @@
+extern crate heliotrope;
+
+use heliotrope::{SolrQuery, SortOrder, GeoPoint, SpatialPredicate};
+
+#[test]
+fn geo_point_parse() {
+    assert_eq!(GeoPoint::parse("45.15,-93.85"), Some(GeoPoint::new(45.15, -93.85)));
+    assert_eq!(GeoPoint::parse("45.15, -93.85"), Some(GeoPoint::new(45.15, -93.85)));
+    assert_eq!(GeoPoint::parse("45.15"), None);
+    assert_eq!(GeoPoint::parse("a,b"), None);
+}
+
+#[test]
+fn geofilt_and_bbox_to_string() {
+    let point = GeoPoint::new(45.15, -93.85);
+    assert_eq!(point.geofilt("store", 5.0).to_string(), "{!geofilt sfield=store pt=45.15,-93.85 d=5}");
+    assert_eq!(point.bbox("store", 0.5).to_string(), "{!bbox sfield=store pt=45.15,-93.85 d=0.5}");
+}
+
+#[test]
+fn query_with_geodist_sort_and_distance_field_to_pairs() {
+    let point = GeoPoint::new(45.15, -93.85);
+    let query = SolrQuery::new("*:*")
+        .add_filter(&point.geofilt("store", 5.0))
+        .add_sort(&point.geodist("store"), SortOrder::Ascending)
+        .add_field(&point.distance_field("dist", "store"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "*:*".to_string()),
+                    ("fl".to_string(), "dist:geodist(store,45.15,-93.85)".to_string()),
+                    ("fq".to_string(), "{!geofilt sfield=store pt=45.15,-93.85 d=5}".to_string()),
+                    ("sort".to_string(), "geodist(store,45.15,-93.85) asc".to_string())));
+}
+
+#[test]
+fn polygon_wkt_is_closed() {
+    let points = [GeoPoint::new(30.0, -10.0), GeoPoint::new(40.0, -40.0), GeoPoint::new(-20.0, -10.0)];
+    assert_eq!(GeoPoint::polygon_wkt(&points), "POLYGON((-10 30, -40 40, -10 -20, -10 30))");
+}
+
+#[test]
+fn spatial_predicate_filter_to_string() {
+    let wkt = "POLYGON((-10 30, -40 40, -10 -20, -10 30))";
+    assert_eq!(SpatialPredicate::Intersects.filter("geo", wkt).to_string(),
+               "geo:\"Intersects(POLYGON((-10 30, -40 40, -10 -20, -10 30)))\"");
+    assert_eq!(SpatialPredicate::IsWithin.filter("geo", wkt).to_string(),
+               "geo:\"IsWithin(POLYGON((-10 30, -40 40, -10 -20, -10 30)))\"");
+}
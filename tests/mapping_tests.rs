@@ -0,0 +1,114 @@
+extern crate heliotrope;
+
+use std::collections::BTreeMap;
+use heliotrope::{SolrDocument, SolrValue, SolrError, SolrErrorKind, SolrQueryResponse};
+use heliotrope::{ToSolrDocument, FromSolrDocument, ToSolrValue, FromSolrValue, DynamicFields};
+
+struct Book {
+    id: String,
+    pages: Option<i32>
+}
+
+impl ToSolrDocument for Book {
+    fn to_solr_document(&self) -> SolrDocument {
+        let mut document = SolrDocument::new();
+        document.add_field("id", &self.id);
+        if let Some(pages) = self.pages {
+            document.add_i64("pages", pages as i64);
+        }
+        document
+    }
+}
+
+impl FromSolrDocument for Book {
+    fn from_solr_document(document: &SolrDocument) -> Result<Book, SolrError> {
+        Ok(Book{id: try!(document.get_str("id")).to_string(),
+                pages: try!(FromSolrValue::from_solr_value(document.get("pages"))
+                    .map_err(|e| SolrError{status: 0, time: 0, message: e, kind: SolrErrorKind::Other}))})
+    }
+}
+
+#[test]
+fn option_should_be_omitted_when_none(){
+    let none: Option<i32> = None;
+    assert_eq!(None, none.to_solr_value());
+    assert_eq!(Some(SolrValue::I64(3)), Some(3i32).to_solr_value());
+    assert_eq!(Ok(None), <Option<i32>>::from_solr_value(Some(&SolrValue::Null)));
+    assert_eq!(Ok(None), <Option<i32>>::from_solr_value(None));
+}
+
+#[test]
+fn vec_should_be_multi_valued(){
+    let empty: Vec<String> = Vec::new();
+    assert_eq!(None, empty.to_solr_value());
+    assert_eq!(Some(SolrValue::Array(vec!(SolrValue::U64(1), SolrValue::U64(2)))), vec!(1u32, 2u32).to_solr_value());
+    let values = SolrValue::Array(vec!(SolrValue::String("a".to_string()), SolrValue::String("b".to_string())));
+    assert_eq!(Ok(vec!("a".to_string(), "b".to_string())), <Vec<String>>::from_solr_value(Some(&values)));
+    assert_eq!(Ok(vec!(true)), <Vec<bool>>::from_solr_value(Some(&SolrValue::Boolean(true))));
+    assert_eq!(Ok(Vec::new()), <Vec<bool>>::from_solr_value(None));
+}
+
+#[test]
+fn integers_should_be_range_checked(){
+    assert_eq!(Ok(-5i8), i8::from_solr_value(Some(&SolrValue::I64(-5))));
+    assert_eq!(Err("300 is out of i8 range".to_string()), i8::from_solr_value(Some(&SolrValue::I64(300))));
+    assert!(u32::from_solr_value(Some(&SolrValue::I64(-1))).is_err());
+    assert_eq!(Err("field not found".to_string()), u32::from_solr_value(None));
+}
+
+#[test]
+fn dynamic_fields_should_match_pattern(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("id", "1");
+    doc.add_field("color_s", "red");
+    doc.add_field("size_s", "XL");
+    doc.add_field("_s", "empty key");
+    let map: BTreeMap<String, String> = DynamicFields::from_dynamic_fields("*_s", &[], &doc).unwrap();
+    assert_eq!(2, map.len());
+    assert_eq!("red", map["color"]);
+    let mut copy = SolrDocument::new();
+    map.add_dynamic_fields("attr_*", &mut copy);
+    assert_eq!(Some(&SolrValue::String("XL".to_string())), copy.get("attr_size"));
+}
+
+#[test]
+fn items_as_should_convert_query_results(){
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":2,"start":0,"docs":[{"id":"1","pages":320},{"id":"2"}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let books: Vec<Book> = response.items_as().ok().unwrap();
+    assert_eq!(2, books.len());
+    assert_eq!("1", books[0].id);
+    assert_eq!(Some(320), books[0].pages);
+    assert_eq!(None, books[1].pages);
+    let doc = books[0].to_solr_document();
+    assert_eq!(Some(&SolrValue::I64(320)), doc.get("pages"));
+}
+
+#[test]
+fn items_as_should_fail_on_invalid_document(){
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"pages":320}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let err = response.items_as::<Book>().err().unwrap();
+    assert_eq!("Field id not found", err.message);
+}
+
+#[test]
+fn dynamic_fields_should_merge_repeated_fields(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("tags_ss", "fantasy");
+    doc.add_field("tags_ss", "humor");
+    let map: BTreeMap<String, Vec<String>> = DynamicFields::from_dynamic_fields("*_ss", &[], &doc).unwrap();
+    assert_eq!(vec!("fantasy".to_string(), "humor".to_string()), map["tags"]);
+}
+
+#[test]
+fn dynamic_fields_should_skip_excluded_fields(){
+    let mut doc = SolrDocument::new();
+    doc.add_field("title_t", "Dragon");
+    doc.add_field("summary_t", "Vikings");
+    let map: BTreeMap<String, String> = DynamicFields::from_dynamic_fields("*_t", &["title_t"], &doc).unwrap();
+    assert_eq!(1, map.len());
+    assert_eq!("Vikings", map["summary"]);
+}
@@ -0,0 +1,117 @@
+extern crate heliotrope;
+
+use heliotrope::{Query, BooleanQuery, LocalParams, escape};
+
+#[test]
+fn escape_special_characters_and_whitespace() {
+    assert_eq!(escape("a:b (c) -d && e || !f"), "a\\:b\\ \\(c\\)\\ \\-d\\ \\&\\&\\ e\\ \\|\\|\\ \\!f");
+    assert_eq!(escape("1/2 [x] {y} ^~*?\"\\+"), "1\\/2\\ \\[x\\]\\ \\{y\\}\\ \\^\\~\\*\\?\\\"\\\\\\+");
+    assert_eq!(escape("dragon"), "dragon");
+}
+
+#[test]
+fn term_query_to_string() {
+    assert_eq!(Query::term("title", "C++ (2nd ed.)").to_string(), "title:C\\+\\+\\ \\(2nd\\ ed.\\)");
+    assert_eq!(Query::text("dragon").to_string(), "dragon");
+    assert_eq!(Query::all().to_string(), "*:*");
+}
+
+#[test]
+fn term_query_should_quote_boolean_operators() {
+    assert_eq!(escape("a;b"), "a\\;b");
+    assert_eq!(Query::term("title", "OR").to_string(), "title:\"OR\"");
+    assert_eq!(Query::text("NOT").to_string(), "\"NOT\"");
+    assert_eq!(Query::wildcard("title", "AND").to_string(), "title:\"AND\"");
+    assert_eq!(Query::term("title", "or").to_string(), "title:or");
+}
+
+#[test]
+fn empty_term_query_should_be_quoted() {
+    assert_eq!(Query::term("title", "").to_string(), "title:\"\"");
+    assert_eq!(Query::wildcard("title", "").to_string(), "title:\"\"");
+    assert_eq!(BooleanQuery::new().must(Query::text("")).should(Query::text("dragon")).to_string(), "(+\"\" dragon)");
+}
+
+#[test]
+fn query_should_escape_field_names() {
+    assert_eq!(Query::term("my title", "dragon").to_string(), "my\\ title:dragon");
+    assert_eq!(Query::term("a:b", "dragon").to_string(), "a\\:b:dragon");
+    assert_eq!(Query::range("a b", Some("1"), None, true, true).to_string(), "a\\ b:[1 TO *]");
+    assert_eq!(Query::field("a:b", Query::text("dragon")).to_string(), "a\\:b:(dragon)");
+    assert_eq!(Query::term("first_name", "dragon").to_string(), "first_name:dragon");
+}
+
+#[test]
+fn phrase_and_proximity_query_to_string() {
+    assert_eq!(Query::phrase("title", "say \"hi\" (now)").to_string(), "title:\"say \\\"hi\\\" (now)\"");
+    assert_eq!(Query::proximity("body", "train dragon", 3).to_string(), "body:\"train dragon\"~3");
+}
+
+#[test]
+fn range_query_to_string() {
+    assert_eq!(Query::range("price", Some("10"), Some("20"), true, false).to_string(), "price:[10 TO 20}");
+    assert_eq!(Query::range("price", None, Some("20"), false, true).to_string(), "price:{* TO 20]");
+    assert_eq!(Query::range("created", Some("2015-01-01T00:00:00Z"), Some("NOW"), true, true).to_string(),
+               "created:[\"2015-01-01T00:00:00Z\" TO NOW]");
+}
+
+#[test]
+fn wildcard_and_fuzzy_query_to_string() {
+    assert_eq!(Query::wildcard("title", "dra?on* (x)").to_string(), "title:dra?on*\\ \\(x\\)");
+    assert_eq!(Query::fuzzy("title", "dragn", 2).to_string(), "title:dragn~2");
+}
+
+#[test]
+fn boolean_query_to_string() {
+    let query: Query = BooleanQuery::new()
+        .must(Query::term("type", "Book"))
+        .should(Query::term("title", "dragon").boost(2.0))
+        .should(Query::phrase("title", "how to train"))
+        .must_not(Query::term("author", "a:b"))
+        .into();
+    assert_eq!(query.to_string(),
+               "(+type:Book title:dragon^2 title:\"how to train\" -author:a\\:b)");
+}
+
+#[test]
+fn only_negative_boolean_query_to_string() {
+    let query = BooleanQuery::new().must_not(Query::term("type", "Comic"));
+    assert_eq!(query.to_string(), "(*:* -type:Comic)");
+}
+
+#[test]
+fn nested_and_field_scoped_query_to_string() {
+    let query = Query::field("title", BooleanQuery::new().should(Query::text("dragon")).should(Query::text("rider")).into())
+        .boost(1.5);
+    assert_eq!(query.to_string(), "(title:(dragon rider))^1.5");
+    assert_eq!(Query::field("title", Query::text("dragon")).to_string(), "title:(dragon)");
+}
+
+#[test]
+fn local_params_query_to_string() {
+    let local_params = LocalParams::new(Some("parent")).param("which", "type:product").param("score", "max");
+    assert_eq!(Query::local_params(local_params, Some(Query::term("color", "red"))).to_string(),
+               "{!parent which=type:product score=max}color:red");
+    let local_params = LocalParams::new(Some("geofilt")).param("sfield", "store").param("pt", "45.15,-93.85");
+    assert_eq!(Query::local_params(local_params, None).to_string(), "{!geofilt sfield=store pt=45.15,-93.85}");
+    assert_eq!(LocalParams::new(None).param("tag", "t").param("v", "it's a test").to_string(),
+               "{!tag=t v='it\\'s a test'}");
+}
+
+#[test]
+fn local_params_boolean_clause_to_string() {
+    let query = BooleanQuery::new()
+        .must(Query::local_params(LocalParams::new(Some("frange")).param("l", "0"), Some(Query::raw("sum(a,b)"))))
+        .must(Query::raw("x:y OR z:w"));
+    assert_eq!(query.to_string(), "(+({!frange l=0}sum(a,b)) +(x:y OR z:w))");
+}
+
+#[test]
+fn block_join_query_to_string() {
+    assert_eq!(Query::parent("type:product", Query::term("color", "red")).to_string(),
+               "{!parent which=type:product}color:red");
+    assert_eq!(Query::child("type:product", Query::term("brand", "Acme Inc")).to_string(),
+               "{!child of=type:product}brand:Acme\\ Inc");
+    assert_eq!(Query::parent("type:product OR type:bundle", Query::all()).to_string(),
+               "{!parent which='type:product OR type:bundle'}*:*");
+}
@@ -2,7 +2,7 @@ extern crate rustc_serialize;
 extern crate heliotrope;
 
 use rustc_serialize::json;
-use heliotrope::{SolrDeleteRequest};
+use heliotrope::{SolrDeleteRequest, SolrDocument, UpdateRequest, AtomicUpdate, Query};
 
 #[test]
 fn solr_delete_request_to_json() {
@@ -17,3 +17,44 @@ fn solr_delete_request_with_many_ids(){
     let json = json::encode(&request);
     assert_eq!(&json.unwrap().to_string(), r#"{"delete":[{"id":"1"},{"id":"2"}]}"#);
 }
+
+#[test]
+fn update_request_with_mixed_commands(){
+    let mut document = SolrDocument::new();
+    document.add_field("id", "1");
+    let request = UpdateRequest::new()
+        .add(&document)
+        .update_fields(&AtomicUpdate::new("2").inc("views", 1))
+        .delete_by_id("3")
+        .delete_by_ids(&["4", "5"])
+        .delete_by_query(&Query::term("type", "Draft"));
+    assert_eq!(json::encode(&request).unwrap(),
+               r#"{"add":{"doc":{"id":"1"}},"add":{"doc":{"id":"2","views":{"inc":1}}},"delete":["3"],"delete":["4","5"],"delete":{"query":"type:Draft"}}"#);
+}
+
+#[test]
+fn update_request_with_commit_options(){
+    let request = UpdateRequest::new()
+        .commit()
+        .soft_commit()
+        .commit_within(5000)
+        .wait_searcher(false)
+        .open_searcher(true)
+        .overwrite(false)
+        .expunge_deletes(true)
+        .optimize()
+        .max_segments(2)
+        .update_chain("dedupe");
+    let pairs = request.to_pairs();
+    let expected = vec!(("wt", "json"), ("commit", "true"), ("softCommit", "true"), ("commitWithin", "5000"),
+                        ("waitSearcher", "false"), ("openSearcher", "true"), ("overwrite", "false"),
+                        ("expungeDeletes", "true"), ("optimize", "true"), ("maxSegments", "2"), ("update.chain", "dedupe"));
+    assert_eq!(pairs, expected.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect::<Vec<_>>());
+}
+
+#[test]
+fn empty_update_request_should_not_commit(){
+    let request = UpdateRequest::new();
+    assert_eq!(json::encode(&request).unwrap(), "{}");
+    assert_eq!(request.to_pairs(), vec!(("wt".to_string(), "json".to_string())));
+}
@@ -2,6 +2,61 @@ extern crate heliotrope;
 extern crate url;
 extern crate hyper;
 
+mod common;
+
 use url::Url;
-use heliotrope::SolrClient;
+use heliotrope::{SolrClient, SolrQuery, SortOrder};
+use common::fake_solr;
+
+#[test]
+fn query_cursor_requires_unique_key_sort() {
+    let client = SolrClient::new(&Url::parse("http://localhost:8983/solr/test/").unwrap());
+    let query = SolrQuery::new("*:*").add_sort("age", SortOrder::Descending);
+    assert!(client.query_cursor(&query, "id").is_err());
+}
+
+#[test]
+fn query_cursor_requires_zero_start() {
+    let client = SolrClient::new(&Url::parse("http://localhost:8983/solr/test/").unwrap());
+    let query = SolrQuery::new("*:*").add_sort("id", SortOrder::Ascending).start(10);
+    assert!(client.query_cursor(&query, "id").is_err());
+    let query = SolrQuery::new("*:*").add_sort("id", SortOrder::Ascending);
+    assert!(client.query_cursor(&query, "id").is_ok());
+}
+
+fn cursor_page(ids: &[&str], next_cursor_mark: &str) -> String {
+    let docs: Vec<String> = ids.iter().map(|id| format!(r#"{{"id":"{}"}}"#, id)).collect();
+    format!(r#"{{"responseHeader":{{"status":0,"QTime":1}},"response":{{"numFound":3,"start":0,"docs":[{}]}},"nextCursorMark":"{}"}}"#,
+            docs.join(","), next_cursor_mark)
+}
+
+#[test]
+fn query_cursor_should_iterate_until_cursor_mark_stops_changing() {
+    let (url, requests) = fake_solr(|n, _| match n {
+        1 => (200, cursor_page(&["1", "2"], "A")),
+        2 => (200, cursor_page(&["3"], "B")),
+        _ => (200, cursor_page(&[], "B"))
+    });
+    let client = SolrClient::new(&url);
+    let query = SolrQuery::new("*:*").add_sort("id", SortOrder::Ascending).rows(2);
+    let ids: Vec<String> = client.query_cursor(&query, "id").unwrap()
+        .map(|doc| doc.ok().unwrap().get_str("id").ok().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec!("1", "2", "3"));
+    let requests = requests.lock().unwrap();
+    assert_eq!(3, requests.len());
+    assert!(requests[0].request_line.contains("cursorMark=*"));
+    assert!(requests[1].request_line.contains("cursorMark=A"));
+    assert!(requests[2].request_line.contains("cursorMark=B"));
+}
 
+#[test]
+fn query_cursor_should_resume_from_query_cursor_mark() {
+    let (url, requests) = fake_solr(|_, _| (200, cursor_page(&["3"], "B")));
+    let client = SolrClient::new(&url);
+    let query = SolrQuery::new("*:*").add_sort("id", SortOrder::Ascending).cursor_mark("A");
+    let mut cursor = client.query_cursor(&query, "id").unwrap();
+    assert_eq!("A", cursor.cursor_mark());
+    assert!(cursor.next().unwrap().is_ok());
+    assert!(requests.lock().unwrap()[0].request_line.contains("cursorMark=A"));
+}
@@ -1,8 +1,10 @@
 extern crate rustc_serialize;
 extern crate heliotrope;
+extern crate time;
 
-use rustc_serialize::json;
-use heliotrope::{SolrDocument};
+use rustc_serialize::json::{self, Json};
+use heliotrope::{SolrDocument, SolrValue, SolrQueryResponse, GeoPoint, DocumentVersion};
+use time::Timespec;
 
 #[test]
 fn empty_document_to_json(){
@@ -36,3 +38,149 @@ fn adding_a_field_really_adds_it(){
     assert_eq!(document.fields.len(), 1);
 }
 
+
+#[test]
+fn document_with_point_field_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_point("store", GeoPoint::new(45.15, -93.85));
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(), r#"{"store":"45.15,-93.85"}"#);
+    assert_eq!(document.get_point("store"), Some(GeoPoint::new(45.15, -93.85)));
+    assert_eq!(document.get_point("missing"), None);
+}
+
+#[test]
+fn document_with_child_documents_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_field("id", "p1");
+    let mut child = SolrDocument::new();
+    child.add_field("id", "v1");
+    document.add_child(child);
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(), r#"{"id":"p1","_childDocuments_":[{"id":"v1"}]}"#);
+}
+
+#[test]
+fn document_with_labelled_child_documents_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_field("id", "p1");
+    let mut first = SolrDocument::new();
+    first.add_field("id", "v1");
+    let mut second = SolrDocument::new();
+    second.add_field("id", "v2");
+    second.add_child(SolrDocument::new());
+    document.add_children("variants", vec!(first, second));
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(),
+               r#"{"id":"p1","variants":[{"id":"v1"},{"id":"v2","_childDocuments_":[{}]}]}"#);
+}
+
+#[test]
+fn many_documents_with_child_documents_to_json(){
+    let mut first = SolrDocument::new();
+    first.add_field("id", "p1");
+    let mut child = SolrDocument::new();
+    child.add_field("id", "v1");
+    first.add_child(child);
+    let mut second = SolrDocument::new();
+    second.add_field("id", "p2");
+    let json = json::encode(&vec!(&first, &second));
+    assert_eq!(json.unwrap().to_string(), r#"[{"id":"p1","_childDocuments_":[{"id":"v1"}]},{"id":"p2"}]"#);
+}
+
+#[test]
+fn document_with_multi_valued_field_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_field("id", "1");
+    document.add_values("tags", &["dragon", "viking"]);
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(), r#"{"id":"1","tags":["dragon","viking"]}"#);
+}
+
+#[test]
+fn document_with_repeated_field_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_field("tags", "dragon");
+    document.add_field("id", "1");
+    document.add_values("tags", &["viking", "island"]);
+    document.add_field("tags", "sheep");
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(), r#"{"tags":["dragon","viking","island","sheep"],"id":"1"}"#);
+    assert_eq!(document.get_values("tags").len(), 4);
+}
+
+#[test]
+fn multi_valued_field_round_trip(){
+    let mut document = SolrDocument::new();
+    document.add_field("id", "1");
+    document.add_values("tags", &["dragon", "viking"]);
+    let json_str = json::encode(&document).unwrap();
+    let parsed = SolrQueryResponse::parse_doc(&Json::from_str(&json_str).unwrap()).unwrap();
+    assert_eq!(parsed.get("tags"), document.get("tags"));
+    assert_eq!(parsed.get_values("tags"),
+               vec!(&SolrValue::String("dragon".to_string()), &SolrValue::String("viking".to_string())));
+}
+
+#[test]
+fn document_with_typed_fields_to_json(){
+    let mut document = SolrDocument::new();
+    document.add_i64("pages", -1);
+    document.add_u64("views", 12);
+    document.add_f64("price", 9.5);
+    document.add_bool("in_stock", true);
+    document.add_date("published", &time::at_utc(Timespec::new(1430483400, 250000000)));
+    document.add_binary("data", b"heliotrope");
+    let json = json::encode(&document);
+    assert_eq!(json.unwrap().to_string(),
+               r#"{"pages":-1,"views":12,"price":9.5,"in_stock":true,"published":"2015-05-01T12:30:00.250Z","data":"aGVsaW90cm9wZQ=="}"#);
+}
+
+#[test]
+fn date_should_omit_zero_milliseconds(){
+    let mut document = SolrDocument::new();
+    document.add_date("published", &time::at_utc(Timespec::new(1430483400, 500)));
+    document.add_date("updated", &time::at_utc(Timespec::new(1430483400, 999999)));
+    assert_eq!(json::encode(&document).unwrap(), r#"{"published":"2015-05-01T12:30:00Z","updated":"2015-05-01T12:30:00Z"}"#);
+}
+
+#[test]
+fn document_typed_getters(){
+    let json = r#"{"pages":320,"delta":-3,"price":9.5,"in_stock":false,"title":"Dragon",
+                   "published":"2015-05-01T12:30:00Z","updated":"2015-05-01T12:30:00.25Z","data":"aGVsaW90cm9wZQ=="}"#;
+    let document = SolrQueryResponse::parse_doc(&Json::from_str(json).unwrap()).unwrap();
+    assert_eq!(document.get_i64("pages").ok(), Some(320));
+    assert_eq!(document.get_u64("pages").ok(), Some(320));
+    assert_eq!(document.get_i64("delta").ok(), Some(-3));
+    assert_eq!(document.get_f64("pages").ok(), Some(320.0));
+    assert_eq!(document.get_f64("price").ok(), Some(9.5));
+    assert_eq!(document.get_bool("in_stock").ok(), Some(false));
+    assert_eq!(document.get_str("title").ok(), Some("Dragon"));
+    assert_eq!(document.get_date("published").ok().map(|x| x.to_timespec()), Some(Timespec::new(1430483400, 0)));
+    assert_eq!(document.get_date("updated").ok().map(|x| x.to_timespec()), Some(Timespec::new(1430483400, 250000000)));
+    assert_eq!(document.get_binary("data").ok(), Some(b"heliotrope".to_vec()));
+}
+
+#[test]
+fn document_typed_getters_type_mismatch(){
+    let json = r#"{"delta":-3,"price":9.5,"title":"Dragon"}"#;
+    let document = SolrQueryResponse::parse_doc(&Json::from_str(json).unwrap()).unwrap();
+    assert!(document.get_u64("delta").is_err());
+    assert!(document.get_i64("price").is_err());
+    assert!(document.get_bool("title").is_err());
+    assert!(document.get_date("title").is_err());
+    assert!(document.get_str("price").is_err());
+    let error = document.get_i64("missing").err().unwrap();
+    assert_eq!(error.message, "Field missing not found");
+}
+
+#[test]
+fn set_version_should_replace_returned_version(){
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1","_version_":1628}]}}"#;
+    let mut doc = SolrQueryResponse::from_json_str(json).ok().unwrap().items.remove(0);
+    assert_eq!(1628, doc.get_version().ok().unwrap());
+    doc.set_version(DocumentVersion::MustExist);
+    assert_eq!(r#"{"id":"1","_version_":1}"#, json::encode(&doc).unwrap());
+    doc.set_version(DocumentVersion::Any);
+    assert_eq!(0, doc.get_version().ok().unwrap());
+}
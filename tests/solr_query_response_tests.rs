@@ -0,0 +1,375 @@
+extern crate heliotrope;
+
+use heliotrope::{SolrQueryResponse, SolrValue, FacetCount, RangeValue, RangeGap};
+use heliotrope::{JsonFacetResult, JsonFacetValue};
+
+#[test]
+fn query_response_without_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1"}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(response.items.len(), 1);
+    assert!(response.facet_counts.is_none());
+}
+
+#[test]
+fn query_response_with_field_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":16,"start":0,"docs":[]},
+                   "facet_counts":{"facet_queries":{},
+                                   "facet_fields":{"city":["Paris",12,"London",3,null,1],
+                                                   "type":[]}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    assert_eq!(facet_counts.fields.len(), 2);
+    let city = facet_counts.field("city").unwrap();
+    assert_eq!(city.counts, vec!(FacetCount{term: "Paris".to_string(), count: 12},
+                                 FacetCount{term: "London".to_string(), count: 3}));
+    assert_eq!(city.missing, Some(1));
+    let kind = facet_counts.field("type").unwrap();
+    assert!(kind.counts.is_empty());
+    assert_eq!(kind.missing, None);
+}
+
+#[test]
+fn query_response_with_malformed_field_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":0,"start":0,"docs":[]},
+                   "facet_counts":{"facet_fields":{"city":["Paris"]}}}"#;
+    assert!(SolrQueryResponse::from_json_str(json).is_err());
+}
+
+#[test]
+fn query_response_with_numeric_range_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_ranges":{"price":{"counts":["0.0",5,"10.0",3],
+                                                            "gap":10.0,"start":0.0,"end":15.0,
+                                                            "before":0,"after":2,"between":8}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    let price = facet_counts.range("price").unwrap();
+    assert_eq!(price.start, RangeValue::Float(0.0));
+    assert_eq!(price.gap, RangeGap::Float(10.0));
+    assert_eq!(price.buckets.len(), 2);
+    assert_eq!(price.buckets[0].start, RangeValue::Float(0.0));
+    assert_eq!(price.buckets[0].end, Some(RangeValue::Float(10.0)));
+    assert_eq!(price.buckets[1].end, Some(RangeValue::Float(15.0)));
+    assert_eq!(price.buckets[1].count, 3);
+    assert_eq!((price.before, price.after, price.between), (Some(0), Some(2), Some(8)));
+}
+
+#[test]
+fn query_response_with_range_gap_out_of_range() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_ranges":{"views":{"counts":["0",5],
+                                                            "gap":18446744073709551615,"start":0,"end":10}}}}"#;
+    let error = SolrQueryResponse::from_json_str(json).err().unwrap();
+    assert!(error.message.starts_with("SolrQueryResponse JSON parsing error (facet_ranges): gap"));
+}
+
+#[test]
+fn query_response_with_range_bucket_near_i64_max() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_ranges":{"views":{"counts":["9223372036854775800",5],
+                                                            "gap":100,"start":9223372036854775800,"end":9223372036854775807}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    let views = facet_counts.range("views").unwrap();
+    assert_eq!(views.buckets[0].end, Some(RangeValue::Int(9223372036854775807)));
+}
+
+#[test]
+fn query_response_with_date_range_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_ranges":{"created":{"counts":["2015-05-01T00:00:00Z",4],
+                                                              "gap":"+1DAY",
+                                                              "start":"2015-05-01T00:00:00Z",
+                                                              "end":"2015-05-02T00:00:00Z"}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    let created = facet_counts.range("created").unwrap();
+    assert_eq!(created.gap, RangeGap::DateMath("+1DAY".to_string()));
+    assert_eq!(created.buckets[0].start, RangeValue::Date("2015-05-01T00:00:00Z".to_string()));
+    assert_eq!(created.buckets[0].end, None);
+    assert_eq!(created.before, None);
+}
+
+#[test]
+fn query_response_with_interval_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_intervals":{"price":{"[10,*]":4,"[*,10)":5,"cheap":1}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    let price = facet_counts.interval("price").unwrap();
+    let keys: Vec<&str> = price.counts.iter().map(|x| x.key.as_ref()).collect();
+    assert_eq!(keys, vec!("[*,10)", "[10,*]", "cheap"));
+    let bounds = price.counts[0].bounds.clone().unwrap();
+    assert_eq!(bounds.start, None);
+    assert_eq!(bounds.end, Some(RangeValue::Int(10)));
+    assert!(bounds.include_start);
+    assert!(!bounds.include_end);
+    assert!(price.counts[2].bounds.is_none());
+}
+
+#[test]
+fn query_response_with_nested_json_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":32,"start":0,"docs":[]},
+                   "facets":{"count":32,"avg_price":12.5,
+                             "categories":{"numBuckets":2,
+                                           "buckets":[{"val":"books","count":20,"avg_price":10.0,
+                                                       "brands":{"buckets":[{"val":"acme","count":3}]}},
+                                                      {"val":"music","count":12,"avg_price":16.7}]},
+                             "cheap":{"count":7,"p":[1.5,9.0]},
+                             "spots":{"gridLevel":1,"columns":2,"rows":1,"minX":-180.0,"maxX":180.0,
+                                      "minY":-90.0,"maxY":90.0,"counts_ints2D":[[0,4]]}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let root = response.json_facets.unwrap();
+    assert_eq!(root.count, 32);
+    assert_eq!(root.metric("avg_price"), Some(&JsonFacetValue::Float(12.5)));
+    match root.facet("categories") {
+        Some(&JsonFacetResult::Buckets(ref categories)) => {
+            assert_eq!(categories.num_buckets, Some(2));
+            assert_eq!(categories.buckets.len(), 2);
+            let books = &categories.buckets[0];
+            assert_eq!(books.val, Some(JsonFacetValue::String("books".to_string())));
+            assert_eq!(books.count, 20);
+            match books.facet("brands") {
+                Some(&JsonFacetResult::Buckets(ref brands)) => assert_eq!(brands.buckets[0].count, 3),
+                _ => panic!("brands sub-facet not parsed")
+            }
+        },
+        _ => panic!("categories facet not parsed")
+    }
+    match root.facet("cheap") {
+        Some(&JsonFacetResult::Bucket(ref cheap)) => {
+            assert_eq!(cheap.count, 7);
+            assert_eq!(cheap.metric("p"),
+                       Some(&JsonFacetValue::List(vec!(JsonFacetValue::Float(1.5), JsonFacetValue::Float(9.0)))));
+        },
+        _ => panic!("cheap facet not parsed")
+    }
+    match root.facet("spots") {
+        Some(&JsonFacetResult::Heatmap(ref spots)) => assert_eq!(spots.counts, Some(vec!(vec!(0, 4)))),
+        _ => panic!("spots facet not parsed")
+    }
+}
+
+#[test]
+fn query_response_with_pivot_facets() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":10,"start":0,"docs":[]},
+                   "facet_counts":{"facet_pivot":{"cat,brand":[
+                       {"field":"cat","value":"books","count":10,
+                        "pivot":[{"field":"brand","value":"acme","count":3},
+                                 {"field":"brand","value":null,"count":1}]},
+                       {"field":"cat","value":"music","count":2}]}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let facet_counts = response.facet_counts.unwrap();
+    let pivot = facet_counts.pivot("cat,brand").unwrap();
+    assert_eq!(pivot.entries.len(), 2);
+    let books = &pivot.entries[0];
+    assert_eq!(books.field, "cat");
+    assert_eq!(books.value, Some("books".to_string()));
+    assert_eq!(books.count, 10);
+    assert_eq!(books.pivot[0].value, Some("acme".to_string()));
+    assert_eq!(books.pivot[1].value, None);
+    assert!(pivot.entries[1].pivot.is_empty());
+}
+
+#[test]
+fn query_response_with_highlighting() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":2,"start":0,"docs":[{"id":"1"},{"id":"2"}]},
+                   "highlighting":{"1":{"title":["How to train your <em>dragon</em>"]},"2":{}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    {
+        let highlighting = response.highlighting.as_ref().unwrap();
+        assert_eq!(highlighting.snippets("1", "title"),
+                   Some(&vec!("How to train your <em>dragon</em>".to_string())));
+        assert_eq!(highlighting.snippets("2", "title"), None);
+    }
+    let items = response.highlighted_items("id");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].1.unwrap().len(), 1);
+    assert!(items[1].1.unwrap().is_empty());
+}
+
+#[test]
+fn query_response_with_grouped_results() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "grouped":{"city":{"matches":16,"ngroups":2,
+                                      "groups":[{"groupValue":"London",
+                                                 "doclist":{"numFound":12,"start":0,"docs":[{"id":"1"}]}},
+                                                {"groupValue":null,
+                                                 "doclist":{"numFound":4,"start":0,"docs":[{"id":"7"}]}}]},
+                              "price:[0 TO 10]":{"matches":16,
+                                                 "doclist":{"numFound":3,"start":0,"docs":[{"id":"2"}]}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    assert!(response.items.is_empty());
+    let city = response.group("city").unwrap();
+    assert_eq!(city.matches, 16);
+    assert_eq!(city.ngroups, Some(2));
+    assert_eq!(city.groups[0].group_value, SolrValue::String("London".to_string()));
+    assert_eq!(city.groups[0].doclist.total, 12);
+    assert_eq!(city.groups[0].doclist.items.len(), 1);
+    assert_eq!(city.groups[1].group_value, SolrValue::Null);
+    let cheap = response.group("price:[0 TO 10]").unwrap();
+    assert!(cheap.groups.is_empty());
+    assert_eq!(cheap.doclist.as_ref().unwrap().total, 3);
+}
+
+#[test]
+fn query_response_with_expanded_results() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1","isbn":"123"}]},
+                   "expanded":{"123":{"numFound":2,"start":0,"docs":[{"id":"4"},{"id":"5"}]}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let expanded = response.expanded.unwrap();
+    assert_eq!(expanded.get("123").unwrap().items.len(), 2);
+}
+
+#[test]
+fn query_response_without_response_and_grouped() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1}}"#;
+    assert!(SolrQueryResponse::from_json_str(json).is_err());
+}
+
+#[test]
+fn query_response_with_next_cursor_mark() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1"}]},
+                   "nextCursorMark":"AoEhMQ=="}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    assert_eq!(response.next_cursor_mark, Some("AoEhMQ==".to_string()));
+}
+
+#[test]
+fn query_response_with_spellcheck() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":0,"start":0,"docs":[]},
+                   "spellcheck":{
+                     "suggestions":[
+                       "dragn",{"numFound":1,"startOffset":0,"endOffset":5,"origFreq":0,
+                                "suggestion":[{"word":"dragon","freq":12}]},
+                       "ridr",{"numFound":1,"startOffset":6,"endOffset":10,"suggestion":["rider"]}],
+                     "correctlySpelled":false,
+                     "collations":[
+                       "collation",{"collationQuery":"dragon rider","hits":3,
+                                    "misspellingsAndCorrections":["dragn","dragon","ridr","rider"]},
+                       "collation","dragon ride"]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let spellcheck = response.spellcheck.unwrap();
+    assert_eq!(spellcheck.correctly_spelled, Some(false));
+    let dragn = spellcheck.suggestion("dragn").unwrap();
+    assert_eq!((dragn.num_found, dragn.start_offset, dragn.end_offset, dragn.orig_freq), (1, 0, 5, Some(0)));
+    assert_eq!(dragn.alternatives[0].word, "dragon");
+    assert_eq!(dragn.alternatives[0].freq, Some(12));
+    assert_eq!(spellcheck.suggestion("ridr").unwrap().alternatives[0].freq, None);
+    assert_eq!(spellcheck.best_collation(), Some("dragon rider"));
+    assert_eq!(spellcheck.collations[0].hits, Some(3));
+    assert_eq!(spellcheck.collations[0].corrections,
+               vec!(("dragn".to_string(), "dragon".to_string()), ("ridr".to_string(), "rider".to_string())));
+    assert_eq!(spellcheck.collations[1].query, "dragon ride");
+    assert_eq!(spellcheck.collations[1].hits, None);
+}
+
+#[test]
+fn query_response_with_more_like_this() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1"}]},
+                   "moreLikeThis":{"1":{"numFound":3,"start":0,"docs":[{"id":"4"},{"id":"7"}]}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let similar = response.more_like_this.unwrap();
+    assert_eq!(similar.get("1").unwrap().total, 3);
+    assert_eq!(similar.get("1").unwrap().items.len(), 2);
+}
+
+#[test]
+fn more_like_this_handler_response_with_match() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "match":{"numFound":1,"start":0,"docs":[{"id":"1"}]},
+                   "response":{"numFound":2,"start":0,"docs":[{"id":"4"},{"id":"7"}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    assert_eq!(response.mlt_match.unwrap().items.len(), 1);
+    assert_eq!(response.items.len(), 2);
+    assert!(response.more_like_this.is_none());
+}
+
+#[test]
+fn query_response_with_stats() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":16,"start":0,"docs":[]},
+                   "stats":{"stats_fields":{
+                     "price":{"min":0.0,"max":399.0,"count":14,"missing":2,"sum":1143.0,
+                              "sumOfSquares":302456.0,"mean":81.64,"stddev":128.23,
+                              "percentiles":["50.0",11.5,"99.0",399.0],"cardinality":12,
+                              "facets":{"inStock":{"true":{"min":1.0,"max":399.0,"count":10}}}},
+                     "name":{"min":"apple","max":"zebra","count":16},
+                     "weight":null}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let stats = response.stats.unwrap();
+    let price = stats.field("price").unwrap();
+    assert_eq!(price.min, Some(SolrValue::F64(0.0)));
+    assert_eq!(price.max, Some(SolrValue::F64(399.0)));
+    assert_eq!((price.count, price.missing), (Some(14), Some(2)));
+    assert_eq!(price.sum, Some(1143.0));
+    assert_eq!(price.mean, Some(81.64));
+    assert_eq!(price.stddev, Some(128.23));
+    assert_eq!(price.percentiles, vec!((50.0, 11.5), (99.0, 399.0)));
+    assert_eq!(price.cardinality, Some(12));
+    assert_eq!(price.facets.get("inStock").unwrap().get("true").unwrap().count, Some(10));
+    assert_eq!(stats.field("name").unwrap().max, Some(SolrValue::String("zebra".to_string())));
+    assert_eq!(stats.field("weight").unwrap().count, None);
+}
+
+#[test]
+fn query_response_with_stats_percentiles_map() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":16,"start":0,"docs":[]},
+                   "stats":{"stats_fields":{"price":{"percentiles":{"10.0":1.5,"5.0":1.0,"50.0":11.5}}}}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let stats = response.stats.unwrap();
+    assert_eq!(stats.field("price").unwrap().percentiles, vec!((5.0, 1.0), (10.0, 1.5), (50.0, 11.5)));
+}
+
+#[test]
+fn query_response_with_nested_child_documents() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[
+                     {"id":"p1","type":"product",
+                      "_childDocuments_":[{"id":"v1","color":"red"},{"id":"v2","color":"blue"}],
+                      "reviews":[{"id":"r1","stars":5}],
+                      "manual":{"id":"m1"}}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let product = &response.items[0];
+    assert_eq!(product.children.len(), 2);
+    assert_eq!(product.children[1].get("color"), Some(&SolrValue::String("blue".to_string())));
+    match product.get("reviews") {
+        Some(&SolrValue::Documents(ref reviews)) => assert_eq!(reviews[0].get("stars"), Some(&SolrValue::U64(5))),
+        other => panic!("unexpected reviews {:?}", other)
+    }
+    match product.get("manual") {
+        Some(&SolrValue::Documents(ref manuals)) => assert_eq!(manuals.len(), 1),
+        other => panic!("unexpected manual {:?}", other)
+    }
+    assert!(product.get("_childDocuments_").is_none());
+}
+
+#[test]
+fn query_response_with_multi_valued_fields() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "response":{"numFound":1,"start":0,"docs":[{"id":"1","tags":["dragon","viking"],"years":[2010,2014],"empty":[]}]}}"#;
+    let response = SolrQueryResponse::from_json_str(json).ok().unwrap();
+    let doc = &response.items[0];
+    assert_eq!(doc.get("tags"), Some(&SolrValue::Array(vec!(SolrValue::String("dragon".to_string()),
+                                                             SolrValue::String("viking".to_string())))));
+    assert_eq!(doc.get("years"), Some(&SolrValue::Array(vec!(SolrValue::U64(2010), SolrValue::U64(2014)))));
+    assert_eq!(doc.get("empty"), Some(&SolrValue::Array(vec!())));
+}
@@ -1,6 +1,13 @@
 extern crate heliotrope;
 
-use heliotrope::{SolrQuery, SortClause, SortOrder};
+use heliotrope::{SolrQuery, SortClause, SortOrder, FacetField, FacetSort};
+use heliotrope::{FacetRange, FacetRangeOther, FacetRangeInclude, FacetInterval, FacetPivot};
+use heliotrope::{JsonFacet, TermsFacet, RangeFacet, QueryFacet, HeatmapFacet, Aggregation};
+use heliotrope::{Highlighting, HighlightMethod};
+use heliotrope::{Grouping, GroupFormat, Collapse, CollapseNullPolicy, Expand};
+use heliotrope::{QueryParser, DisMax, MinimumMatch};
+use heliotrope::{Query, Spellcheck, MoreLikeThis, MoreLikeThisQuery, MoreLikeThisSeed};
+use heliotrope::{StatsField, Stat, ChildTransformer};
 
 #[test]
 fn query_only_query_to_pairs() {
@@ -149,3 +156,460 @@ fn query_and_custom_start_and_rows() {
                     ("start".to_string(), "125".to_string()),
                     ("rows".to_string(), "25".to_string())));
 }
+
+#[test]
+fn query_and_facet_field_to_pairs() {
+    let query = SolrQuery::new("abba").add_facet_field(FacetField::new("city"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.field".to_string(), "city".to_string())));
+}
+
+#[test]
+fn query_and_global_facet_options_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_facet_field(FacetField::new("city"))
+        .facet_limit(5)
+        .facet_mincount(1)
+        .facet_sort(FacetSort::Index)
+        .facet_prefix("Lo")
+        .facet_missing(true);
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.limit".to_string(), "5".to_string()),
+                    ("facet.mincount".to_string(), "1".to_string()),
+                    ("facet.sort".to_string(), "index".to_string()),
+                    ("facet.prefix".to_string(), "Lo".to_string()),
+                    ("facet.missing".to_string(), "true".to_string()),
+                    ("facet.field".to_string(), "city".to_string())));
+}
+
+#[test]
+fn query_and_per_field_facet_options_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .set_facet_fields(&[FacetField::new("city").limit(-1).sort(FacetSort::Count),
+                            FacetField::new("type").mincount(2)]);
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.field".to_string(), "city".to_string()),
+                    ("f.city.facet.limit".to_string(), "-1".to_string()),
+                    ("f.city.facet.sort".to_string(), "count".to_string()),
+                    ("facet.field".to_string(), "type".to_string()),
+                    ("f.type.facet.mincount".to_string(), "2".to_string())));
+}
+
+#[test]
+fn query_and_numeric_facet_range_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_facet_range(FacetRange::numeric("price", 0.0, 100.0, 10.0)
+                         .hardend(true)
+                         .add_other(FacetRangeOther::Before)
+                         .add_other(FacetRangeOther::After)
+                         .add_include(FacetRangeInclude::Lower));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.range".to_string(), "price".to_string()),
+                    ("f.price.facet.range.start".to_string(), "0".to_string()),
+                    ("f.price.facet.range.end".to_string(), "100".to_string()),
+                    ("f.price.facet.range.gap".to_string(), "10".to_string()),
+                    ("f.price.facet.range.hardend".to_string(), "true".to_string()),
+                    ("f.price.facet.range.other".to_string(), "before".to_string()),
+                    ("f.price.facet.range.other".to_string(), "after".to_string()),
+                    ("f.price.facet.range.include".to_string(), "lower".to_string())));
+}
+
+#[test]
+fn query_and_date_facet_range_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_facet_range(FacetRange::new("created", "NOW/DAY-7DAYS", "NOW/DAY", "+1DAY"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.range".to_string(), "created".to_string()),
+                    ("f.created.facet.range.start".to_string(), "NOW/DAY-7DAYS".to_string()),
+                    ("f.created.facet.range.end".to_string(), "NOW/DAY".to_string()),
+                    ("f.created.facet.range.gap".to_string(), "+1DAY".to_string())));
+}
+
+#[test]
+fn query_and_facet_interval_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_facet_interval(FacetInterval::new("price")
+                            .add_interval(None, Some("10"), true, false)
+                            .add_keyed_interval("expensive", Some("10"), None, true, true));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.interval".to_string(), "price".to_string()),
+                    ("f.price.facet.interval.set".to_string(), "[*,10)".to_string()),
+                    ("f.price.facet.interval.set".to_string(), "{!key=expensive}[10,*]".to_string())));
+}
+
+#[test]
+fn query_and_json_facets_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_json_facet("categories", TermsFacet::new("cat")
+                        .limit(5)
+                        .add_facet("avg_price", Aggregation::Avg("price".to_string()))
+                        .add_facet("p", Aggregation::Percentile("price".to_string(), vec!(50.0, 99.9))))
+        .add_json_facet("cheap", QueryFacet::new("price:[* TO 10]"))
+        .add_json_facet("total", Aggregation::Sum("price".to_string()));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("json.facet".to_string(),
+                     concat!(r#"{"categories":{"facet":{"avg_price":"avg(price)","p":"percentile(price,50,99.9)"},"#,
+                             r#""field":"cat","limit":5,"type":"terms"},"#,
+                             r#""cheap":{"q":"price:[* TO 10]","type":"query"},"#,
+                             r#""total":"sum(price)"}"#).to_string())));
+}
+
+#[test]
+fn json_range_and_heatmap_facets_to_json() {
+    let range = RangeFacet::numeric("price", 0.0, 100.0, 25.0)
+        .hardend(true)
+        .add_other(FacetRangeOther::All)
+        .add_facet("uniq", Aggregation::Unique("brand".to_string()));
+    assert_eq!(JsonFacet::from(range).to_json().to_string(),
+               concat!(r#"{"end":"100","facet":{"uniq":"unique(brand)"},"field":"price","gap":"25","#,
+                       r#""hardend":true,"other":["all"],"start":"0","type":"range"}"#));
+    let heatmap = HeatmapFacet::new("location").grid_level(2).format("png");
+    assert_eq!(JsonFacet::from(heatmap).to_json().to_string(),
+               r#"{"field":"location","format":"png","gridLevel":2,"type":"heatmap"}"#);
+}
+
+#[test]
+fn query_and_tagged_filter_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_tagged_filter("city", "city:London")
+        .add_tagged_filter("type", "{!term f=type}Book");
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("fq".to_string(), "{!tag=city}city:London".to_string()),
+                    ("fq".to_string(), "{!term f=type tag=type}Book".to_string())));
+}
+
+#[test]
+fn query_and_multi_select_facets_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_tagged_filter("city", "city:London")
+        .add_facet_field(FacetField::new("city").exclude("city").key("all cities"))
+        .add_facet_range(FacetRange::new("price", "0", "100", "50").exclude("city").exclude("type"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("fq".to_string(), "{!tag=city}city:London".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.field".to_string(), "{!ex=city key='all cities'}city".to_string()),
+                    ("facet.range".to_string(), "{!ex=city,type}price".to_string()),
+                    ("f.price.facet.range.start".to_string(), "0".to_string()),
+                    ("f.price.facet.range.end".to_string(), "100".to_string()),
+                    ("f.price.facet.range.gap".to_string(), "50".to_string())));
+}
+
+#[test]
+fn keyed_interval_should_quote_key() {
+    let facet = FacetInterval::new("price").add_keyed_interval("10 or more", Some("10"), None, true, true);
+    assert_eq!(facet.to_pairs()[1].1, "{!key='10 or more'}[10,*]");
+}
+
+#[test]
+fn facet_local_params_should_be_quoted() {
+    assert_eq!(FacetField::new("city").key("it's").to_pairs()[0].1, r"{!key='it\'s'}city");
+    assert_eq!(FacetField::new("city").key("a}b").to_pairs()[0].1, "{!key='a}b'}city");
+    assert_eq!(FacetField::new("city").key("a=b").to_pairs()[0].1, "{!key='a=b'}city");
+    assert_eq!(FacetField::new("city").key("a\tb").exclude("x}").to_pairs()[0].1, "{!ex='x}' key='a\tb'}city");
+}
+
+#[test]
+fn query_and_facet_pivot_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_facet_pivot(FacetPivot::new(&["cat", "brand"]).exclude("brand"))
+        .facet_pivot_mincount(1);
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("facet".to_string(), "true".to_string()),
+                    ("facet.pivot".to_string(), "{!ex=brand}cat,brand".to_string()),
+                    ("facet.pivot.mincount".to_string(), "1".to_string())));
+}
+
+#[test]
+fn query_and_highlighting_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .highlight(Highlighting::new()
+                   .add_field("title")
+                   .add_field("body")
+                   .method(HighlightMethod::Original)
+                   .snippets(3)
+                   .fragsize(120)
+                   .tags("<b>", "</b>")
+                   .query("dragon")
+                   .require_field_match(true));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("hl".to_string(), "true".to_string()),
+                    ("hl.fl".to_string(), "title,body".to_string()),
+                    ("hl.method".to_string(), "original".to_string()),
+                    ("hl.snippets".to_string(), "3".to_string()),
+                    ("hl.fragsize".to_string(), "120".to_string()),
+                    ("hl.simple.pre".to_string(), "<b>".to_string()),
+                    ("hl.simple.post".to_string(), "</b>".to_string()),
+                    ("hl.q".to_string(), "dragon".to_string()),
+                    ("hl.requireFieldMatch".to_string(), "true".to_string())));
+}
+
+#[test]
+fn query_and_unified_highlighting_tags_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .highlight(Highlighting::new().method(HighlightMethod::Unified).tags("<b>", "</b>"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("hl".to_string(), "true".to_string()),
+                    ("hl.method".to_string(), "unified".to_string()),
+                    ("hl.simple.pre".to_string(), "<b>".to_string()),
+                    ("hl.tag.pre".to_string(), "<b>".to_string()),
+                    ("hl.simple.post".to_string(), "</b>".to_string()),
+                    ("hl.tag.post".to_string(), "</b>".to_string())));
+}
+
+#[test]
+fn query_and_grouping_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .group(Grouping::new()
+               .add_field("city")
+               .add_query("price:[0 TO 10]")
+               .add_func("floor(price)")
+               .limit(3)
+               .add_sort("price", SortOrder::Ascending)
+               .ngroups(true)
+               .format(GroupFormat::Simple));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("group".to_string(), "true".to_string()),
+                    ("group.field".to_string(), "city".to_string()),
+                    ("group.query".to_string(), "price:[0 TO 10]".to_string()),
+                    ("group.func".to_string(), "floor(price)".to_string()),
+                    ("group.limit".to_string(), "3".to_string()),
+                    ("group.sort".to_string(), "price asc".to_string()),
+                    ("group.ngroups".to_string(), "true".to_string()),
+                    ("group.format".to_string(), "simple".to_string())));
+}
+
+#[test]
+fn query_and_collapse_expand_to_pairs() {
+    let query = SolrQuery::new("abba")
+        .add_filter("type:Book")
+        .collapse(Collapse::new("isbn").min("price").null_policy(CollapseNullPolicy::Expand))
+        .expand(Expand::new().rows(5).sort("price asc"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("fq".to_string(), "type:Book".to_string()),
+                    ("fq".to_string(), "{!collapse field=isbn min=price nullPolicy=expand}".to_string()),
+                    ("expand".to_string(), "true".to_string()),
+                    ("expand.rows".to_string(), "5".to_string()),
+                    ("expand.sort".to_string(), "price asc".to_string())));
+}
+
+#[test]
+fn query_and_cursor_mark_to_pairs() {
+    let query = SolrQuery::new("abba").add_sort("id", SortOrder::Ascending).rows(100).cursor_mark("*");
+    assert!(query.is_sorted_by("id"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "abba".to_string()),
+                    ("sort".to_string(), "id asc".to_string()),
+                    ("rows".to_string(), "100".to_string()),
+                    ("cursorMark".to_string(), "*".to_string())));
+}
+
+#[test]
+fn query_and_edismax_to_pairs() {
+    let query = SolrQuery::new("dragon")
+        .dismax(DisMax::new()
+                .add_qf("title", Some(2.0))
+                .add_qf("body", None)
+                .add_pf("title", Some(1.5))
+                .add_pf2("body", None)
+                .ps(2)
+                .qs(1)
+                .mm(MinimumMatch::Conditional(vec!((2, MinimumMatch::Percent(-25)), (9, MinimumMatch::Count(-3)))))
+                .tie(0.1)
+                .add_bq("type:Book^2")
+                .add_bf("recip(ms(NOW,created),3.16e-11,1,1)")
+                .add_boost("log(popularity)")
+                .uf("title body")
+                .lowercase_operators(false)
+                .stopwords(true));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "dragon".to_string()),
+                    ("defType".to_string(), "edismax".to_string()),
+                    ("qf".to_string(), "title^2 body".to_string()),
+                    ("pf".to_string(), "title^1.5".to_string()),
+                    ("pf2".to_string(), "body".to_string()),
+                    ("ps".to_string(), "2".to_string()),
+                    ("qs".to_string(), "1".to_string()),
+                    ("mm".to_string(), "2<-25% 9<-3".to_string()),
+                    ("tie".to_string(), "0.1".to_string()),
+                    ("bq".to_string(), "type:Book^2".to_string()),
+                    ("bf".to_string(), "recip(ms(NOW,created),3.16e-11,1,1)".to_string()),
+                    ("boost".to_string(), "log(popularity)".to_string()),
+                    ("uf".to_string(), "title body".to_string()),
+                    ("lowercaseOperators".to_string(), "false".to_string()),
+                    ("stopwords".to_string(), "true".to_string())));
+}
+
+#[test]
+fn query_and_dismax_def_type_to_pairs() {
+    let query = SolrQuery::new("dragon")
+        .def_type(QueryParser::DisMax)
+        .dismax(DisMax::new().add_qf("title", None).mm(MinimumMatch::Percent(75)));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "dragon".to_string()),
+                    ("defType".to_string(), "dismax".to_string()),
+                    ("qf".to_string(), "title".to_string()),
+                    ("mm".to_string(), "75%".to_string())));
+}
+
+#[test]
+fn query_with_typed_query_and_filter_to_pairs() {
+    let query = SolrQuery::new(&Query::term("title", "dragon: (2)"))
+        .add_filter(&Query::range("year", Some("2000"), None, true, true))
+        .add_tagged_filter("t", &Query::term("type", "Book"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "title:dragon\\:\\ \\(2\\)".to_string()),
+                    ("fq".to_string(), "year:[2000 TO *]".to_string()),
+                    ("fq".to_string(), "{!tag=t}type:Book".to_string())));
+}
+
+#[test]
+fn query_and_spellcheck_to_pairs() {
+    let query = SolrQuery::new("dragn").spellcheck(Spellcheck::new()
+        .query("dragn ridr")
+        .add_dictionary("default")
+        .add_dictionary("wordbreak")
+        .count(5)
+        .collate(true)
+        .max_collations(3)
+        .max_collation_tries(10)
+        .collate_extended_results(true)
+        .extended_results(true)
+        .only_more_popular(false));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "dragn".to_string()),
+                    ("spellcheck".to_string(), "true".to_string()),
+                    ("spellcheck.q".to_string(), "dragn ridr".to_string()),
+                    ("spellcheck.dictionary".to_string(), "default".to_string()),
+                    ("spellcheck.dictionary".to_string(), "wordbreak".to_string()),
+                    ("spellcheck.count".to_string(), "5".to_string()),
+                    ("spellcheck.collate".to_string(), "true".to_string()),
+                    ("spellcheck.maxCollations".to_string(), "3".to_string()),
+                    ("spellcheck.maxCollationTries".to_string(), "10".to_string()),
+                    ("spellcheck.collateExtendedResults".to_string(), "true".to_string()),
+                    ("spellcheck.extendedResults".to_string(), "true".to_string()),
+                    ("spellcheck.onlyMorePopular".to_string(), "false".to_string())));
+}
+
+#[test]
+fn query_and_more_like_this_to_pairs() {
+    let query = SolrQuery::new("id:1").more_like_this(MoreLikeThis::new()
+        .add_field("title")
+        .add_field("body")
+        .min_tf(1)
+        .min_df(2)
+        .count(3)
+        .boost(true)
+        .add_qf("title", Some(2.0))
+        .add_qf("body", None));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "id:1".to_string()),
+                    ("mlt".to_string(), "true".to_string()),
+                    ("mlt.fl".to_string(), "title,body".to_string()),
+                    ("mlt.mintf".to_string(), "1".to_string()),
+                    ("mlt.mindf".to_string(), "2".to_string()),
+                    ("mlt.count".to_string(), "3".to_string()),
+                    ("mlt.boost".to_string(), "true".to_string()),
+                    ("mlt.qf".to_string(), "title^2 body".to_string())));
+}
+
+#[test]
+fn more_like_this_handler_query_to_pairs() {
+    let query = MoreLikeThisQuery::from_query("id:1")
+        .options(MoreLikeThis::new().add_field("body").max_query_terms(10))
+        .add_field("id")
+        .add_filter("type:Book")
+        .rows(5)
+        .match_include(true);
+    assert_eq!(query.handler, "mlt");
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "id:1".to_string()),
+                    ("fl".to_string(), "id".to_string()),
+                    ("fq".to_string(), "type:Book".to_string()),
+                    ("rows".to_string(), "5".to_string()),
+                    ("mlt.fl".to_string(), "body".to_string()),
+                    ("mlt.maxqt".to_string(), "10".to_string()),
+                    ("mlt.match.include".to_string(), "true".to_string())));
+    let query = MoreLikeThisQuery::from_text("Vala Morgulis").options(MoreLikeThis::new().add_field("body"));
+    assert_eq!(query.seed, MoreLikeThisSeed::Text("Vala Morgulis".to_string()));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("mlt.fl".to_string(), "body".to_string())));
+}
+
+#[test]
+fn query_and_stats_fields_to_pairs() {
+    let query = SolrQuery::new("*:*")
+        .add_stats_field(StatsField::new("price")
+            .add_stat(Stat::Min)
+            .add_stat(Stat::Max)
+            .percentiles(&[50.0, 99.9])
+            .cardinality(0.5)
+            .add_facet("inStock"))
+        .add_stats_field(StatsField::new("weight").exclude("t").key("item weight"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "*:*".to_string()),
+                    ("stats".to_string(), "true".to_string()),
+                    ("stats.field".to_string(), "{!min=true max=true percentiles=50,99.9 cardinality=0.5}price".to_string()),
+                    ("f.price.stats.facet".to_string(), "inStock".to_string()),
+                    ("stats.field".to_string(), "{!ex=t key='item weight'}weight".to_string())));
+}
+
+#[test]
+fn query_and_child_transformer_to_pairs() {
+    let query = SolrQuery::new("*:*")
+        .add_field("id")
+        .add_child_transformer(ChildTransformer::new()
+            .parent_filter("type:product")
+            .child_filter("color:red OR color:blue")
+            .limit(5)
+            .add_field("id")
+            .add_field("color"));
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("q".to_string(), "*:*".to_string()),
+                    ("fl".to_string(), "id, [child parentFilter=type:product childFilter='color:red OR color:blue' limit=5 fl=id,color]".to_string())));
+    let query = SolrQuery::new("*:*").add_child_transformer(ChildTransformer::new());
+    assert_eq!(query.to_pairs()[2], ("fl".to_string(), "[child]".to_string()));
+}
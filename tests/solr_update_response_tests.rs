@@ -0,0 +1,48 @@
+extern crate heliotrope;
+
+use heliotrope::{SolrUpdateResponse, SolrErrorKind, VersionConflict};
+
+#[test]
+fn update_response_should_parse_success(){
+    let response = SolrUpdateResponse::from_json_str(r#"{"responseHeader":{"status":0,"QTime":12}}"#).ok().unwrap();
+    assert_eq!(0, response.status);
+    assert_eq!(12, response.time);
+}
+
+#[test]
+fn update_response_should_parse_version_conflict(){
+    let json = r#"{"responseHeader":{"status":409,"QTime":2},
+                   "error":{"metadata":["error-class","org.apache.solr.common.SolrException"],
+                            "msg":"version conflict for book-1 expected=1628 actual=1631","code":409}}"#;
+    let err = SolrUpdateResponse::from_json_str(json).err().unwrap();
+    assert_eq!(409, err.status);
+    assert_eq!(SolrErrorKind::VersionConflict(VersionConflict{id: "book-1".to_string(), expected: Some(1628), actual: Some(1631)}),
+               err.kind);
+}
+
+#[test]
+fn update_response_should_parse_missing_document_conflict(){
+    let json = r#"{"responseHeader":{"status":409,"QTime":2},
+                   "error":{"msg":"Document not found for update.  id=book-2","code":409}}"#;
+    let err = SolrUpdateResponse::from_json_str(json).err().unwrap();
+    assert_eq!(SolrErrorKind::VersionConflict(VersionConflict{id: "book-2".to_string(), expected: Some(1), actual: None}),
+               err.kind);
+}
+
+#[test]
+fn update_response_should_parse_other_errors(){
+    let json = r#"{"responseHeader":{"status":400,"QTime":2},
+                   "error":{"msg":"ERROR: [doc=1] unknown field 'foo'","code":400}}"#;
+    let err = SolrUpdateResponse::from_json_str(json).err().unwrap();
+    assert_eq!(400, err.status);
+    assert_eq!(SolrErrorKind::Other, err.kind);
+    assert_eq!("ERROR: [doc=1] unknown field 'foo'", err.message);
+}
+
+#[test]
+fn version_conflict_should_parse_existing_document_message(){
+    let conflict = VersionConflict::parse("version conflict for 7 expected=-1 actual=1631").unwrap();
+    assert_eq!(Some(-1), conflict.expected);
+    assert_eq!(Some(1631), conflict.actual);
+    assert!(VersionConflict::parse("unknown field").is_none());
+}
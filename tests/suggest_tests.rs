@@ -0,0 +1,84 @@
+extern crate heliotrope;
+
+use heliotrope::{SuggestQuery, SolrSuggestResponse};
+
+#[test]
+fn suggest_query_to_pairs() {
+    let query = SuggestQuery::new("drag")
+        .add_dictionary("titleSuggester")
+        .add_dictionary("authorSuggester")
+        .count(5)
+        .cfq("Book")
+        .build(false)
+        .reload(false);
+    assert_eq!(query.handler, "suggest");
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("suggest".to_string(), "true".to_string()),
+                    ("suggest.q".to_string(), "drag".to_string()),
+                    ("suggest.dictionary".to_string(), "titleSuggester".to_string()),
+                    ("suggest.dictionary".to_string(), "authorSuggester".to_string()),
+                    ("suggest.count".to_string(), "5".to_string()),
+                    ("suggest.cfq".to_string(), "Book".to_string()),
+                    ("suggest.build".to_string(), "false".to_string()),
+                    ("suggest.reload".to_string(), "false".to_string())));
+}
+
+#[test]
+fn suggest_build_query_to_pairs() {
+    let query = SuggestQuery::empty().handler("autocomplete").build_all(true);
+    assert_eq!(query.handler, "autocomplete");
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("suggest".to_string(), "true".to_string()),
+                    ("suggest.buildAll".to_string(), "true".to_string())));
+}
+
+#[test]
+fn suggest_response_from_json() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":2},
+                   "suggest":{"titleSuggester":{"drag":{"numFound":2,"suggestions":[
+                       {"term":"dragon","weight":12,"payload":"book-1"},
+                       {"term":"dragonfly","weight":3,"payload":""}]}}}}"#;
+    let response = SolrSuggestResponse::from_json_str(json).ok().unwrap();
+    assert_eq!(response.time, 2);
+    let result = response.dictionary("titleSuggester").unwrap();
+    assert_eq!(result.query, "drag");
+    assert_eq!(result.num_found, 2);
+    assert_eq!(result.suggestions[0].term, "dragon");
+    assert_eq!(result.suggestions[0].weight, 12);
+    assert_eq!(result.suggestions[0].payload, Some("book-1".to_string()));
+    assert_eq!(result.suggestions[1].payload, None);
+}
+
+#[test]
+fn suggest_build_response_from_json() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":120},"command":"build"}"#;
+    let response = SolrSuggestResponse::from_json_str(json).ok().unwrap();
+    assert!(response.dictionaries.is_empty());
+}
+
+#[test]
+fn suggest_response_without_suggestions_list() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":2},"suggest":{"titleSuggester":{"drag":{"numFound":0}}}}"#;
+    assert!(SolrSuggestResponse::from_json_str(json).is_err());
+}
+
+#[test]
+fn suggest_response_with_several_queries() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":2},
+                   "suggest":{"titleSuggester":{"drag":{"numFound":1,"suggestions":[{"term":"dragon","weight":12}]},
+                                                "vik":{"numFound":1,"suggestions":[{"term":"viking","weight":5}]}}}}"#;
+    let response = SolrSuggestResponse::from_json_str(json).ok().unwrap();
+    let queries: Vec<&str> = response.dictionaries["titleSuggester"].iter().map(|x| x.query.as_ref()).collect();
+    assert_eq!(queries, vec!("drag", "vik"));
+}
+
+#[test]
+fn suggest_error_response_should_be_error() {
+    let json = r#"{"responseHeader":{"status":400,"QTime":1},
+                   "error":{"msg":"No suggester named unknown was configured","code":400}}"#;
+    let error = SolrSuggestResponse::from_json_str(json).err().unwrap();
+    assert_eq!(error.status, 400);
+    assert_eq!(error.message, "No suggester named unknown was configured");
+}
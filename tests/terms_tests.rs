@@ -0,0 +1,56 @@
+extern crate heliotrope;
+
+use heliotrope::{TermsQuery, TermsSort, SolrTermsResponse};
+
+#[test]
+fn terms_query_to_pairs() {
+    let query = TermsQuery::new("cat")
+        .add_field("manu")
+        .prefix("ele")
+        .regex("e.*s")
+        .lower("a", true)
+        .upper("z", false)
+        .mincount(1)
+        .maxcount(100)
+        .limit(-1)
+        .sort(TermsSort::Index)
+        .stats(true);
+    assert_eq!(query.handler, "terms");
+    assert_eq!(query.to_pairs(),
+               vec!(("wt".to_string(), "json".to_string()),
+                    ("terms".to_string(), "true".to_string()),
+                    ("terms.fl".to_string(), "cat".to_string()),
+                    ("terms.fl".to_string(), "manu".to_string()),
+                    ("terms.prefix".to_string(), "ele".to_string()),
+                    ("terms.regex".to_string(), "e.*s".to_string()),
+                    ("terms.lower".to_string(), "a".to_string()),
+                    ("terms.lower.incl".to_string(), "true".to_string()),
+                    ("terms.upper".to_string(), "z".to_string()),
+                    ("terms.upper.incl".to_string(), "false".to_string()),
+                    ("terms.mincount".to_string(), "1".to_string()),
+                    ("terms.maxcount".to_string(), "100".to_string()),
+                    ("terms.limit".to_string(), "-1".to_string()),
+                    ("terms.sort".to_string(), "index".to_string()),
+                    ("terms.stats".to_string(), "true".to_string())));
+}
+
+#[test]
+fn terms_response_from_json() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1},
+                   "terms":{"cat":["electronics",14,"currency",4],"manu":[]},
+                   "indexstats":{"numDocs":32}}"#;
+    let response = SolrTermsResponse::from_json_str(json).ok().unwrap();
+    let cat = response.field("cat").unwrap();
+    assert_eq!(cat.len(), 2);
+    assert_eq!(cat[0].term, "electronics");
+    assert_eq!(cat[0].frequency, 14);
+    assert_eq!(cat[1].term, "currency");
+    assert!(response.field("manu").unwrap().is_empty());
+    assert_eq!(response.num_docs, Some(32));
+}
+
+#[test]
+fn terms_response_without_terms() {
+    let json = r#"{"responseHeader":{"status":0,"QTime":1}}"#;
+    assert!(SolrTermsResponse::from_json_str(json).is_err());
+}
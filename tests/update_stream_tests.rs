@@ -0,0 +1,55 @@
+extern crate heliotrope;
+extern crate url;
+
+mod common;
+
+use std::io::Cursor;
+use heliotrope::{SolrClient, UpdateFormat, CsvOptions};
+use common::fake_solr;
+
+fn solr_ok() -> (u16, String) {
+    (200, r#"{"responseHeader":{"status":0,"QTime":3}}"#.to_string())
+}
+
+#[test]
+fn update_stream_should_send_json_lines_to_json_docs_handler(){
+    let (url, requests) = fake_solr(|_, _| solr_ok());
+    let mut body = Cursor::new("{\"id\":\"1\"}\n{\"id\":\"2\"}\n");
+    let response = SolrClient::new(&url).update_stream(&UpdateFormat::JsonLines, &mut body).ok().unwrap();
+    assert_eq!(3, response.time);
+    let received = &requests.lock().unwrap()[0];
+    assert_eq!("POST /solr/test/update/json/docs?wt=json HTTP/1.1", received.request_line);
+    assert!(received.content_type.starts_with("application/json"));
+    assert_eq!("{\"id\":\"1\"}\n{\"id\":\"2\"}\n", received.body);
+}
+
+#[test]
+fn update_stream_should_send_csv_with_params(){
+    let (url, requests) = fake_solr(|_, _| solr_ok());
+    let mut body = Cursor::new("1;Moby Dick;Herman Melville\n");
+    let format = UpdateFormat::Csv(CsvOptions::new().separator(';').header(false).fieldnames(&["id", "title", "authors"]).split_field("authors", '|'));
+    SolrClient::new(&url).update_stream_and_commit(&format, &mut body).ok().unwrap();
+    let received = &requests.lock().unwrap()[0];
+    assert_eq!("POST /solr/test/update?wt=json&separator=%3B&header=false&fieldnames=id%2Ctitle%2Cauthors&f.authors.split=true&f.authors.separator=%7C&commit=true HTTP/1.1",
+               received.request_line);
+    assert_eq!("application/csv", received.content_type);
+    assert_eq!("1;Moby Dick;Herman Melville\n", received.body);
+}
+
+#[test]
+fn update_stream_should_send_xml(){
+    let (url, requests) = fake_solr(|_, _| solr_ok());
+    let xml = "<add><doc><field name=\"id\">1</field></doc></add>";
+    SolrClient::new(&url).update_stream(&UpdateFormat::Xml, &mut xml.as_bytes()).ok().unwrap();
+    let received = &requests.lock().unwrap()[0];
+    assert_eq!("POST /solr/test/update?wt=json HTTP/1.1", received.request_line);
+    assert_eq!("application/xml", received.content_type);
+    assert_eq!(xml, received.body);
+}
+
+#[test]
+fn csv_options_to_pairs(){
+    let pairs = CsvOptions::new().split(true).to_pairs();
+    assert_eq!(vec!(("split".to_string(), "true".to_string())), pairs);
+    assert_eq!(vec!(("wt".to_string(), "json".to_string())), UpdateFormat::Json.to_pairs());
+}